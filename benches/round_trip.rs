@@ -0,0 +1,73 @@
+// SPDX-FileCopyrightText: The cirque authors
+// SPDX-License-Identifier: MPL-2.0
+
+//! Round trips of items through the ring
+//!
+//! Each round trip consists of pushing an item, popping it, pushing it
+//! back, and recycling its node.
+
+use std::{
+    hint::black_box,
+    thread,
+    time::{Duration, Instant},
+};
+
+use cirque::{new_producer_consumer, recyclers::NoOpRecycler};
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+
+const BATCH_SIZE: usize = 64;
+
+fn single_thread(c: &mut Criterion) {
+    let mut group = c.benchmark_group("round_trip");
+    group.throughput(Throughput::Elements(BATCH_SIZE as u64));
+    group.bench_function("single_thread", |b| {
+        let (mut producer, mut consumer) = new_producer_consumer(NoOpRecycler, BATCH_SIZE);
+        b.iter(|| {
+            for item in 0..BATCH_SIZE {
+                producer.push(black_box(item));
+            }
+            while let Some(item) = consumer.pop() {
+                black_box(*item);
+                consumer.push_back(item);
+            }
+            producer.drain_and_recycle();
+        });
+    });
+    group.finish();
+}
+
+fn cross_thread(c: &mut Criterion) {
+    let mut group = c.benchmark_group("round_trip");
+    group.throughput(Throughput::Elements(BATCH_SIZE as u64));
+    group.bench_function("cross_thread", |b| {
+        b.iter_custom(|iters| {
+            let (mut producer, mut consumer) = new_producer_consumer(NoOpRecycler, BATCH_SIZE);
+            let count = iters as usize * BATCH_SIZE;
+            let consumer_thread = thread::spawn(move || {
+                let mut popped = 0;
+                while popped < count {
+                    if let Some(item) = consumer.pop() {
+                        black_box(*item);
+                        consumer.push_back(item);
+                        popped += 1;
+                    }
+                }
+            });
+            let start = Instant::now();
+            for item in 0..count {
+                producer.push(black_box(item));
+                if item % BATCH_SIZE == 0 {
+                    producer.drain_and_recycle();
+                }
+            }
+            consumer_thread.join().unwrap();
+            let elapsed = start.elapsed();
+            black_box(producer.stats().snapshot());
+            elapsed.max(Duration::from_nanos(1))
+        });
+    });
+    group.finish();
+}
+
+criterion_group!(benches, single_thread, cross_thread);
+criterion_main!(benches);
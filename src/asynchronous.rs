@@ -0,0 +1,165 @@
+// SPDX-FileCopyrightText: The cirque authors
+// SPDX-License-Identifier: MPL-2.0
+
+//! Asynchronous waiting on both sides of the queue
+
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use atomic_waker::AtomicWaker;
+use futures_core::Stream;
+
+use crate::{
+    backend::{LinkedList, RingBackend},
+    ConsumableItem, Consumer, Producer, Recycler,
+};
+
+/// Waker slots shared by the [`Producer`] and the [`Consumer`]
+///
+/// Registering and taking a waker is lock-free. Waking invokes the
+/// waker of the executor on the thread of the waking side, i.e. the
+/// producer is woken in the realtime context of the consumer.
+#[derive(Default)]
+pub(crate) struct Wakers {
+    /// Woken by the consumer when returning items
+    pub(crate) producer: AtomicWaker,
+
+    /// Woken by the producer when pushing new items
+    pub(crate) consumer: AtomicWaker,
+}
+
+/// Future returned by [`Producer::recycled()`]
+///
+/// Resolves with the number of items that have been returned by the
+/// [`Consumer`] and recycled.
+#[must_use = "futures do nothing unless polled"]
+#[allow(missing_debug_implementations)]
+pub struct Recycled<'a, T, R, B = LinkedList>
+where
+    R: Recycler<T>,
+    B: RingBackend<T>,
+{
+    producer: &'a mut Producer<T, R, B>,
+}
+
+impl<'a, T, R, B> Recycled<'a, T, R, B>
+where
+    R: Recycler<T>,
+    B: RingBackend<T>,
+{
+    pub(crate) fn new(producer: &'a mut Producer<T, R, B>) -> Self {
+        Self { producer }
+    }
+}
+
+impl<T, R, B> Future for Recycled<'_, T, R, B>
+where
+    R: Recycler<T>,
+    B: RingBackend<T>,
+{
+    type Output = usize;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let producer = &mut *self.get_mut().producer;
+        let mut count = producer.recycle_returned();
+        if count == 0 {
+            producer.wakers.producer.register(cx.waker());
+            // Check again to not miss items that have been returned
+            // before the waker has been registered.
+            count = producer.recycle_returned();
+            if count == 0 {
+                return Poll::Pending;
+            }
+        }
+        // Unregister the waker to not let the consumer wake and drop
+        // it later in the realtime context.
+        drop(producer.wakers.producer.take());
+        Poll::Ready(count)
+    }
+}
+
+impl<T, R, B> Drop for Recycled<'_, T, R, B>
+where
+    R: Recycler<T>,
+    B: RingBackend<T>,
+{
+    fn drop(&mut self) {
+        drop(self.producer.wakers.producer.take());
+    }
+}
+
+/// Stream adapter returned by [`Consumer::stream()`]
+///
+/// Yields the pushed items in order. The stream terminates after the
+/// [`Producer`] has been dropped and all pending items have been yielded.
+#[must_use = "streams do nothing unless polled"]
+#[allow(missing_debug_implementations)]
+pub struct ConsumerStream<'a, T, B = LinkedList>
+where
+    B: RingBackend<T>,
+{
+    consumer: &'a mut Consumer<T, B>,
+}
+
+impl<'a, T, B> ConsumerStream<'a, T, B>
+where
+    B: RingBackend<T>,
+{
+    pub(crate) fn new(consumer: &'a mut Consumer<T, B>) -> Self {
+        Self { consumer }
+    }
+}
+
+impl<T, B> Stream for ConsumerStream<'_, T, B>
+where
+    B: RingBackend<T>,
+{
+    type Item = ConsumableItem<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let consumer = &mut *self.get_mut().consumer;
+        let mut next = poll_next_item(consumer);
+        if next.is_pending() {
+            consumer.wakers.consumer.register(cx.waker());
+            // Check again to not miss items that have been pushed
+            // before the waker has been registered.
+            next = poll_next_item(consumer);
+            if next.is_pending() {
+                return Poll::Pending;
+            }
+        }
+        // Unregister the waker to not let the producer wake and drop
+        // it later.
+        drop(consumer.wakers.consumer.take());
+        next
+    }
+}
+
+impl<T, B> Drop for ConsumerStream<'_, T, B>
+where
+    B: RingBackend<T>,
+{
+    fn drop(&mut self) {
+        drop(self.consumer.wakers.consumer.take());
+    }
+}
+
+/// Pop the next item or detect the end of the stream
+fn poll_next_item<T, B>(consumer: &mut Consumer<T, B>) -> Poll<Option<ConsumableItem<T>>>
+where
+    B: RingBackend<T>,
+{
+    // Check the producer first to not miss any items that have been
+    // pushed before it has been dropped.
+    let is_producer_alive = consumer.is_producer_alive();
+    if let Some(item) = consumer.pop() {
+        return Poll::Ready(Some(item));
+    }
+    if is_producer_alive {
+        return Poll::Pending;
+    }
+    Poll::Ready(None)
+}
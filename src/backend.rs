@@ -0,0 +1,77 @@
+// SPDX-FileCopyrightText: The cirque authors
+// SPDX-License-Identifier: MPL-2.0
+
+//! Transport of nodes between the producer and the consumer
+//!
+//! The ring consists of two unidirectional SPSC queues that carry
+//! [`Node`]s, one for pushing items to the consumer and one for
+//! returning them to the producer. Both queues are created by a
+//! [`RingBackend`].
+//!
+//! Backends must neither block nor (de-)allocate memory when pushing
+//! or popping nodes, because both happens in the realtime context.
+
+use llq::Queue;
+
+use crate::Node;
+
+/// Sending half of a queue
+pub trait NodeTx<T> {
+    /// Push a node into the queue
+    fn push(&mut self, node: Node<T>);
+}
+
+/// Receiving half of a queue
+pub trait NodeRx<T> {
+    /// Pop the next node from the queue
+    fn pop(&mut self) -> Option<Node<T>>;
+}
+
+/// Factory for the queues of a ring
+pub trait RingBackend<T> {
+    /// Sending half of a queue
+    type Tx: NodeTx<T>;
+
+    /// Receiving half of a queue
+    type Rx: NodeRx<T>;
+
+    /// Create a new queue and split it into its sending and receiving half
+    fn new_queue(&mut self) -> (Self::Tx, Self::Rx);
+}
+
+/// Default backend with the wait-free linked-list queues of [`llq`]
+///
+/// Nodes are linked intrusively, i.e. the capacity of the queues is
+/// unbounded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LinkedList;
+
+/// Sending half of a [`LinkedList`] queue
+#[allow(missing_debug_implementations)]
+pub struct LinkedListTx<T>(llq::Producer<T>);
+
+/// Receiving half of a [`LinkedList`] queue
+#[allow(missing_debug_implementations)]
+pub struct LinkedListRx<T>(llq::Consumer<T>);
+
+impl<T> NodeTx<T> for LinkedListTx<T> {
+    fn push(&mut self, node: Node<T>) {
+        self.0.push(node);
+    }
+}
+
+impl<T> NodeRx<T> for LinkedListRx<T> {
+    fn pop(&mut self) -> Option<Node<T>> {
+        self.0.pop()
+    }
+}
+
+impl<T> RingBackend<T> for LinkedList {
+    type Tx = LinkedListTx<T>;
+    type Rx = LinkedListRx<T>;
+
+    fn new_queue(&mut self) -> (Self::Tx, Self::Rx) {
+        let (tx, rx) = Queue::new().split();
+        (LinkedListTx(tx), LinkedListRx(rx))
+    }
+}
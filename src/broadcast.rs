@@ -0,0 +1,96 @@
+// SPDX-FileCopyrightText: The cirque authors
+// SPDX-License-Identifier: MPL-2.0
+
+//! Broadcasting to multiple realtime consumers
+//!
+//! A single producer publishes each item into separate rings, one per
+//! consumer. The items are cloned on the non-realtime side. Each ring has
+//! its own return path for recycling, i.e. no consumer ever allocates or
+//! deallocates memory.
+
+use alloc::vec::Vec;
+use core::iter;
+
+use crate::{new_producer_consumer, Consumer, Producer, Recycler, Stats};
+
+/// Create a new broadcast producer and the corresponding consumers.
+///
+/// Each ring gets its own copy of `recycler` and recycles up to
+/// `recycling_capacity` nodes.
+///
+/// # Panics
+///
+/// Panics if `consumer_count` is 0.
+#[must_use]
+pub fn new_producer_consumers<T, R>(
+    recycler: R,
+    recycling_capacity: usize,
+    consumer_count: usize,
+) -> (BroadcastProducer<T, R>, Vec<Consumer<T>>)
+where
+    R: Recycler<T> + Clone,
+{
+    assert!(consumer_count > 0, "consumer count must not be 0");
+    let (producers, consumers) = iter::repeat_n(recycler, consumer_count)
+        .map(|recycler| new_producer_consumer(recycler, recycling_capacity))
+        .unzip();
+    (BroadcastProducer { producers }, consumers)
+}
+
+/// Non-realtime producer that publishes to multiple consumers
+#[allow(missing_debug_implementations)]
+pub struct BroadcastProducer<T, R>
+where
+    R: Recycler<T>,
+{
+    producers: Vec<Producer<T, R>>,
+}
+
+impl<T, R> BroadcastProducer<T, R>
+where
+    T: Clone,
+    R: Recycler<T>,
+{
+    /// The number of consumers
+    #[must_use]
+    pub fn consumer_count(&self) -> usize {
+        self.producers.len()
+    }
+
+    /// Push a new item to all consumers
+    ///
+    /// The item is cloned into reused nodes, i.e. resources of recycled
+    /// items are retained. The last consumer receives the item itself.
+    pub fn push(&mut self, item: T) {
+        let Some((last, others)) = self.producers.split_last_mut() else {
+            unreachable!("at least one consumer");
+        };
+        for producer in others {
+            producer.push_clone(&item);
+        }
+        last.push(item);
+    }
+
+    /// Drain all consumed items of all consumers and recycle as much as possible
+    ///
+    /// See also: [`Producer::drain_and_recycle()`]
+    pub fn drain_and_recycle(&mut self) {
+        for producer in &mut self.producers {
+            producer.drain_and_recycle();
+        }
+    }
+
+    /// Tune the recycling capacity per consumer
+    ///
+    /// See also: [`Producer::tune_recycling_capacity()`]
+    pub fn tune_recycling_capacity(&mut self, recycling_capacity: usize) {
+        for producer in &mut self.producers {
+            producer.tune_recycling_capacity(recycling_capacity);
+        }
+    }
+
+    /// Statistics of the queues, ordered by consumer
+    pub fn stats(&self) -> impl ExactSizeIterator<Item = &Stats> {
+        self.producers.iter().map(Producer::stats)
+    }
+}
@@ -0,0 +1,42 @@
+// SPDX-FileCopyrightText: The cirque authors
+// SPDX-License-Identifier: MPL-2.0
+
+//! Padding to avoid false sharing
+
+use core::ops::{Deref, DerefMut};
+
+/// Aligns the value to the size of a cache line
+///
+/// Some architectures prefetch pairs of cache lines.
+#[cfg_attr(
+    any(
+        target_arch = "x86_64",
+        target_arch = "aarch64",
+        target_arch = "powerpc64",
+    ),
+    repr(align(128))
+)]
+#[cfg_attr(
+    not(any(
+        target_arch = "x86_64",
+        target_arch = "aarch64",
+        target_arch = "powerpc64",
+    )),
+    repr(align(64))
+)]
+#[derive(Debug, Default)]
+pub(crate) struct CachePadded<T>(pub(crate) T);
+
+impl<T> Deref for CachePadded<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for CachePadded<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
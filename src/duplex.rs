@@ -0,0 +1,161 @@
+// SPDX-FileCopyrightText: The cirque authors
+// SPDX-License-Identifier: MPL-2.0
+
+//! Bidirectional request/response exchange
+//!
+//! The realtime side attaches a reply to each [`Exchange`] before pushing
+//! it back. The reply travels back to the producer in the same node and
+//! could be received by [`Producer::pop_reply()`].
+
+use alloc::collections::VecDeque;
+
+use crate::{Recycler, Stats, TryPushError};
+
+/// Request with a slot for the reply
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Exchange<Req, Resp> {
+    request: Req,
+    reply: Option<Resp>,
+}
+
+impl<Req, Resp> Exchange<Req, Resp> {
+    /// The request
+    #[must_use]
+    pub const fn request(&self) -> &Req {
+        &self.request
+    }
+
+    /// The request (mutable)
+    pub fn request_mut(&mut self) -> &mut Req {
+        &mut self.request
+    }
+
+    /// Attach a reply
+    ///
+    /// Replaces any previously attached reply.
+    pub fn reply(&mut self, reply: Resp) {
+        self.reply = Some(reply);
+    }
+
+    /// Check if a reply has been attached
+    #[must_use]
+    pub const fn has_reply(&self) -> bool {
+        self.reply.is_some()
+    }
+}
+
+/// Collects the replies and recycles the requests
+struct ExchangeRecycler<Resp, R> {
+    recycler: R,
+    replies: VecDeque<Resp>,
+}
+
+impl<Req, Resp, R> Recycler<Exchange<Req, Resp>> for ExchangeRecycler<Resp, R>
+where
+    R: Recycler<Req>,
+{
+    fn recycle(&mut self, item: &mut Exchange<Req, Resp>) {
+        let Exchange { request, reply } = item;
+        self.replies.extend(reply.take());
+        self.recycler.recycle(request);
+    }
+
+    fn dispose(&mut self, item: Exchange<Req, Resp>) {
+        let Exchange { request, reply } = item;
+        self.replies.extend(reply);
+        self.recycler.dispose(request);
+    }
+
+    fn capacity(&self, item: &Exchange<Req, Resp>) -> Option<usize> {
+        self.recycler.capacity(&item.request)
+    }
+
+    fn heap_size(&self, item: &Exchange<Req, Resp>) -> usize {
+        self.recycler.heap_size(&item.request)
+    }
+}
+
+/// Realtime consumer of requests
+pub type Consumer<Req, Resp> = crate::Consumer<Exchange<Req, Resp>>;
+
+/// Create a new producer/consumer circular queue for exchanging requests and replies.
+#[must_use]
+pub fn new_producer_consumer<Req, Resp, R>(
+    recycler: R,
+    recycling_capacity: usize,
+) -> (Producer<Req, Resp, R>, Consumer<Req, Resp>)
+where
+    R: Recycler<Req>,
+{
+    let recycler = ExchangeRecycler {
+        recycler,
+        replies: VecDeque::new(),
+    };
+    let (inner, consumer) = crate::new_producer_consumer(recycler, recycling_capacity);
+    (Producer { inner }, consumer)
+}
+
+/// Non-realtime producer of requests that receives replies
+#[allow(missing_debug_implementations)]
+pub struct Producer<Req, Resp, R>
+where
+    R: Recycler<Req>,
+{
+    inner: crate::Producer<Exchange<Req, Resp>, ExchangeRecycler<Resp, R>>,
+}
+
+impl<Req, Resp, R> Producer<Req, Resp, R>
+where
+    R: Recycler<Req>,
+{
+    /// Push a new request into the queue
+    ///
+    /// See also: [`crate::Producer::push()`]
+    pub fn push(&mut self, request: Req) {
+        self.inner.push(Exchange {
+            request,
+            reply: None,
+        });
+    }
+
+    /// Try to push a new request into the queue
+    ///
+    /// See also: [`crate::Producer::try_push()`]
+    ///
+    /// # Errors
+    ///
+    /// Returns the rejected request.
+    pub fn try_push(&mut self, request: Req) -> Result<(), TryPushError<Req>> {
+        self.inner
+            .try_push(Exchange {
+                request,
+                reply: None,
+            })
+            .map_err(|err| err.map(|exchange| exchange.request))
+    }
+
+    /// Pop the next reply
+    ///
+    /// Drains and recycles all returned requests if no reply is pending.
+    /// Replies are received in the order in which the requests have been
+    /// pushed back by the consumer.
+    pub fn pop_reply(&mut self) -> Option<Resp> {
+        if self.inner.recycler.replies.is_empty() {
+            self.inner.drain_and_recycle();
+        }
+        self.inner.recycler.replies.pop_front()
+    }
+
+    /// Tune the recycling capacity
+    ///
+    /// See also: [`crate::Producer::tune_recycling_capacity()`]
+    pub fn tune_recycling_capacity(&mut self, recycling_capacity: usize) {
+        self.inner.tune_recycling_capacity(recycling_capacity);
+    }
+
+    /// Statistics of the queue
+    #[must_use]
+    pub fn stats(&self) -> &Stats {
+        self.inner.stats()
+    }
+}
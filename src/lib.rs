@@ -12,10 +12,53 @@
 #![warn(clippy::pedantic)]
 #![warn(clippy::clone_on_ref_ptr)]
 #![warn(rustdoc::broken_intra_doc_links)]
+#![cfg_attr(not(feature = "std"), no_std)]
 
-use std::ops::{Deref, DerefMut};
+extern crate alloc;
 
-use llq::{Node, Queue};
+use core::{
+    error::Error,
+    fmt,
+    ops::{Deref, DerefMut},
+};
+
+pub use llq::Node;
+
+mod cache_padded;
+
+pub mod backend;
+use backend::{LinkedList, NodeRx, NodeTx, RingBackend};
+
+mod shutdown;
+use shutdown::Shutdown;
+
+mod size_class;
+use size_class::SizeClasses;
+
+mod sync;
+use sync::Arc;
+
+mod stats;
+pub use stats::{Stats, StatsSnapshot};
+
+pub mod broadcast;
+pub mod duplex;
+#[cfg(feature = "std")]
+pub mod mpsc;
+pub mod priority;
+pub mod recyclers;
+pub mod schedule;
+pub mod swap;
+
+#[cfg(feature = "async")]
+mod asynchronous;
+#[cfg(feature = "async")]
+use asynchronous::Wakers;
+#[cfg(feature = "async")]
+pub use asynchronous::{ConsumerStream, Recycled};
+
+#[cfg(feature = "rt-check")]
+pub mod rt_check;
 
 /// Non-realtime recycler
 ///
@@ -24,10 +67,49 @@ use llq::{Node, Queue};
 /// realtime context, e.g. dropping a buffer.
 ///
 /// Items might be dropped at any time without recycling them. Don't rely
-/// on that the method in this trait will be called for every consumed item!
+/// on that [`Recycler::recycle()`] will be called for every consumed item!
+///
+/// Items that are discarded by the [`Producer`] are handed over to
+/// [`Recycler::dispose()`]. This includes items that exceed the recycling
+/// capacity and the previous values of reused nodes that are replaced by
+/// new items. Items of reused nodes that are filled in-place, e.g. by
+/// [`Producer::push_with()`], are recycled instead. Items that are dropped
+/// elsewhere are not disposed. This includes items that are still pending
+/// or returned to the [`Consumer`] after the [`Producer`] has been dropped.
 pub trait Recycler<T> {
     /// Recycle an item
     fn recycle(&mut self, item: &mut T);
+
+    /// Dispose an item that is about to be discarded
+    ///
+    /// The default implementation simply drops the item.
+    fn dispose(&mut self, item: T) {
+        drop(item);
+    }
+
+    /// The capacity of the resources retained by an item, e.g. a buffer
+    ///
+    /// Recycled nodes are ordered into size classes by this capacity for
+    /// [`Producer::push_with_capacity()`]. Invoked after recycling the item.
+    ///
+    /// The default implementation returns `None`, i.e. the capacity is
+    /// unknown.
+    fn capacity(&self, item: &T) -> Option<usize> {
+        let _ = item;
+        None
+    }
+
+    /// The number of bytes retained by an item on the heap
+    ///
+    /// Accounted for the recycling budget, see
+    /// [`Producer::set_recycling_budget()`]. Invoked after recycling
+    /// the item.
+    ///
+    /// The default implementation returns 0.
+    fn heap_size(&self, item: &T) -> usize {
+        let _ = item;
+        0
+    }
 }
 
 /// Create a new producer/consumer circular queue.
@@ -35,78 +117,722 @@ pub trait Recycler<T> {
 pub fn new_producer_consumer<T, R>(
     recycler: R,
     recycling_capacity: usize,
-) -> (Producer<T, R>, Consumer<T>) {
-    let (producer_tx, consumer_rx) = Queue::new().split();
-    let (consumer_tx, producer_rx) = Queue::new().split();
+) -> (Producer<T, R>, Consumer<T>)
+where
+    R: Recycler<T>,
+{
+    new_producer_consumer_with_backend(recycler, recycling_capacity, LinkedList)
+}
+
+/// Create a new producer/consumer circular queue with a custom backend
+/// for transporting the nodes.
+#[must_use]
+pub fn new_producer_consumer_with_backend<T, R, B>(
+    recycler: R,
+    recycling_capacity: usize,
+    mut backend: B,
+) -> (Producer<T, R, B>, Consumer<T, B>)
+where
+    R: Recycler<T>,
+    B: RingBackend<T>,
+{
+    let (producer_tx, consumer_rx) = backend.new_queue();
+    let (consumer_tx, producer_rx) = backend.new_queue();
+    let stats = Arc::new(Stats::default());
+    let shutdown = Arc::new(Shutdown::default());
+    #[cfg(feature = "async")]
+    let wakers = Arc::new(Wakers::default());
     let producer = Producer {
         tx: producer_tx,
         rx: producer_rx,
         recycler,
         recycling_capacity,
-        recycled_nodes: Vec::with_capacity(recycling_capacity),
+        recycled_nodes: SizeClasses::with_capacity(recycling_capacity),
+        pool_size: None,
+        in_flight: 0,
+        in_flight_limit: None,
+        recycling_budget: None,
+        stats: Arc::clone(&stats),
+        shutdown: Arc::clone(&shutdown),
+        #[cfg(feature = "async")]
+        wakers: Arc::clone(&wakers),
     };
     let consumer = Consumer {
         rx: consumer_rx,
         tx: consumer_tx,
+        stats,
+        shutdown,
+        #[cfg(feature = "async")]
+        wakers,
     };
     (producer, consumer)
 }
 
+/// Create a new producer/consumer circular queue with a preallocated pool of nodes.
+///
+/// Allocates `pool_size` nodes up front that are initialized with `new_item`.
+/// The recycling capacity is set to `pool_size` to keep all nodes circling.
+///
+/// The producer operates in bounded-allocation mode, i.e. [`Producer::try_push()`]
+/// and [`Producer::try_push_with()`] fail instead of allocating new nodes when
+/// the pool is exhausted. The nodes of the pool are never dropped, even when
+/// lowering the recycling capacity. This imposes a hard limit on the memory
+/// occupied by the queue as long as only these methods are used for pushing.
+/// All other methods for pushing still allocate new nodes when the pool is
+/// exhausted.
+#[must_use]
+pub fn new_producer_consumer_bounded<T, R>(
+    recycler: R,
+    pool_size: usize,
+    mut new_item: impl FnMut() -> T,
+) -> (Producer<T, R>, Consumer<T>)
+where
+    R: Recycler<T>,
+{
+    let (mut producer, consumer) = new_producer_consumer(recycler, pool_size);
+    for _ in 0..pool_size {
+        let node = producer.allocate_node(new_item());
+        producer.push_recycled_node(node);
+    }
+    producer.pool_size = Some(pool_size);
+    (producer, consumer)
+}
+
+/// Error returned by [`Producer::try_push()`]
+///
+/// Hands back the rejected item, or the rejected closure for filling
+/// an item in-place.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TryPushError<T> {
+    /// No reusable node is available and allocating new nodes is not permitted
+    Exhausted(T),
+
+    /// The limit of items in flight has been reached
+    Full(T),
+
+    /// The consumer has been dropped
+    Disconnected(T),
+
+    /// A shutdown has been requested by [`Producer::shutdown()`]
+    ShutDown(T),
+}
+
+impl<T> TryPushError<T> {
+    /// Recover the rejected item
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        match self {
+            Self::Exhausted(item)
+            | Self::Full(item)
+            | Self::Disconnected(item)
+            | Self::ShutDown(item) => item,
+        }
+    }
+
+    /// Map the rejected item
+    #[must_use]
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> TryPushError<U> {
+        match self {
+            Self::Exhausted(item) => TryPushError::Exhausted(f(item)),
+            Self::Full(item) => TryPushError::Full(f(item)),
+            Self::Disconnected(item) => TryPushError::Disconnected(f(item)),
+            Self::ShutDown(item) => TryPushError::ShutDown(f(item)),
+        }
+    }
+}
+
+impl<T> fmt::Display for TryPushError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Exhausted(_) => f.write_str("node pool exhausted"),
+            Self::Full(_) => f.write_str("too many items in flight"),
+            Self::Disconnected(_) => f.write_str("consumer disconnected"),
+            Self::ShutDown(_) => f.write_str("shutdown requested"),
+        }
+    }
+}
+
+impl<T> Error for TryPushError<T> where T: fmt::Debug {}
+
 /// Non-realtime producer
 #[allow(missing_debug_implementations)]
-pub struct Producer<T, R> {
-    tx: llq::Producer<T>,
-    rx: llq::Consumer<T>,
+pub struct Producer<T, R, B = LinkedList>
+where
+    R: Recycler<T>,
+    B: RingBackend<T>,
+{
+    tx: B::Tx,
+    rx: B::Rx,
     recycler: R,
     recycling_capacity: usize,
-    recycled_nodes: Vec<llq::Node<T>>,
+    recycled_nodes: SizeClasses<T>,
+    /// The number of preallocated nodes in bounded-allocation mode
+    pool_size: Option<usize>,
+    /// The number of pushed nodes that have not been received back yet
+    in_flight: usize,
+    in_flight_limit: Option<usize>,
+    /// The maximum heap size of all recycled items in bytes
+    recycling_budget: Option<usize>,
+    stats: Arc<Stats>,
+    shutdown: Arc<Shutdown>,
+    #[cfg(feature = "async")]
+    wakers: Arc<Wakers>,
 }
 
-impl<T, R> Producer<T, R>
+impl<T, R, B> Producer<T, R, B>
 where
     R: Recycler<T>,
+    B: RingBackend<T>,
 {
     /// Push a new item into the queue
+    ///
+    /// Allocates a new node if no reusable node is available, even
+    /// in bounded-allocation mode. Ignores the limit of items in flight.
     pub fn push(&mut self, item: T) {
         let node = self.new_node(item);
+        self.push_node(node);
+    }
+
+    /// Try to push a new item into the queue
+    ///
+    /// In bounded-allocation mode the item is handed back if no reusable
+    /// node is available. Otherwise a new node is allocated like in
+    /// [`Self::push()`].
+    ///
+    /// The item is also handed back if the limit of items in flight
+    /// has been reached, even after receiving all returned items, or if
+    /// the consumer has been dropped, or after requesting a shutdown.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TryPushError::Exhausted`] if the node pool is exhausted,
+    /// [`TryPushError::Full`] if too many items are in flight,
+    /// [`TryPushError::Disconnected`] if the consumer has been dropped,
+    /// and [`TryPushError::ShutDown`] after [`Self::shutdown()`].
+    pub fn try_push(&mut self, item: T) -> Result<(), TryPushError<T>> {
+        let node = self.try_new_node(item)?;
+        self.push_node(node);
+        Ok(())
+    }
+
+    /// Push a new item into the queue by filling it in-place
+    ///
+    /// The item of a reused node is recycled and then handed out for
+    /// modification, i.e. any resources like buffers are retained.
+    /// A new item is created by [`Default::default()`] if no reusable
+    /// node is available.
+    pub fn push_with(&mut self, fill: impl FnOnce(&mut T))
+    where
+        T: Default,
+    {
+        let mut node = self
+            .pop_recycled_node()
+            .unwrap_or_else(|| self.allocate_node(T::default()));
+        fill(&mut *node);
+        self.push_node(node);
+    }
+
+    /// Try to push a new item into the queue by filling it in-place
+    ///
+    /// Behaves like [`Self::push_with()`], but hands back `fill` instead
+    /// of pushing like [`Self::try_push()`].
+    ///
+    /// # Errors
+    ///
+    /// See [`Self::try_push()`].
+    pub fn try_push_with<F>(&mut self, fill: F) -> Result<(), TryPushError<F>>
+    where
+        T: Default,
+        F: FnOnce(&mut T),
+    {
+        if !self.is_consumer_alive() {
+            return Err(TryPushError::Disconnected(fill));
+        }
+        if self.shutdown.is_requested() {
+            return Err(TryPushError::ShutDown(fill));
+        }
+        if self.is_in_flight_limit_reached() {
+            return Err(TryPushError::Full(fill));
+        }
+        let mut node = if let Some(node) = self.pop_recycled_node() {
+            node
+        } else if self.pool_size.is_some() {
+            return Err(TryPushError::Exhausted(fill));
+        } else {
+            self.allocate_node(T::default())
+        };
+        fill(&mut *node);
+        self.push_node(node);
+        Ok(())
+    }
+
+    /// Push a new item into the queue by filling it in-place, preferring
+    /// a node whose item already has at least `min_capacity`
+    ///
+    /// Picks a matching recycled node by the [`Recycler::capacity()`] of
+    /// its item. Otherwise returned items are received and recycled until
+    /// a matching one is found. Returned items that don't match are kept
+    /// as recycled nodes while there is room. If the recycling capacity
+    /// is exhausted the next returned node is picked regardless of its
+    /// capacity instead of dropping it. Finally, the recycled node with
+    /// the largest capacity is picked, or a new item is created like in
+    /// [`Self::push_with()`].
+    pub fn push_with_capacity(&mut self, min_capacity: usize, fill: impl FnOnce(&mut T))
+    where
+        T: Default,
+    {
+        let mut node = self
+            .pop_recycled_node_with_capacity(min_capacity)
+            .unwrap_or_else(|| self.allocate_node(T::default()));
+        fill(&mut *node);
+        self.push_node(node);
+    }
+
+    /// Push a clone of an item into the queue
+    ///
+    /// Behaves like [`Self::push_with()`], i.e. the item of a reused node
+    /// is recycled and then overwritten in-place by [`Clone::clone_from()`]
+    /// to retain its resources.
+    pub(crate) fn push_clone(&mut self, item: &T)
+    where
+        T: Clone,
+    {
+        let node = if let Some(mut node) = self.pop_recycled_node() {
+            (*node).clone_from(item);
+            node
+        } else {
+            self.allocate_node(item.clone())
+        };
+        self.push_node(node);
+    }
+
+    /// Push multiple new items into the queue
+    ///
+    /// Equivalent to invoking [`Self::push()`] for each item. A consumer
+    /// waiting asynchronously is only woken after all items have been
+    /// pushed.
+    pub fn push_iter(&mut self, items: impl IntoIterator<Item = T>) {
+        for item in items {
+            let node = self.new_node(item);
+            self.enqueue_node(node);
+        }
+        self.notify_consumer();
+    }
+
+    pub(crate) fn push_node(&mut self, node: Node<T>) {
+        self.enqueue_node(node);
+        self.notify_consumer();
+    }
+
+    /// Push a node through a separate queue that shares the return path
+    pub(crate) fn push_node_via(&mut self, tx: &mut B::Tx, node: Node<T>) {
+        tx.push(node);
+        self.in_flight += 1;
+        self.stats.record_pushed();
+        self.notify_consumer();
+    }
+
+    fn enqueue_node(&mut self, node: Node<T>) {
         self.tx.push(node);
+        self.in_flight += 1;
+        self.stats.record_pushed();
     }
 
-    fn new_node(&mut self, item: T) -> llq::Node<T> {
-        // Reuse the recycled nodes first, because this does not involve any memory barriers.
-        if let Some(mut node) = self.recycled_nodes.pop().or_else(|| self.rx.pop()) {
-            let _ = std::mem::replace(&mut *node, item);
-            return node;
+    pub(crate) fn new_node(&mut self, item: T) -> Node<T> {
+        // Allocate a new node if none could be reused
+        self.reuse_node(item)
+            .unwrap_or_else(|item| self.allocate_node(item))
+    }
+
+    /// Reuse an existing node or allocate a new one unless in bounded-allocation mode,
+    /// the limit of items in flight has been reached, the consumer has been dropped,
+    /// or a shutdown has been requested
+    pub(crate) fn try_new_node(&mut self, item: T) -> Result<Node<T>, TryPushError<T>> {
+        if !self.is_consumer_alive() {
+            return Err(TryPushError::Disconnected(item));
+        }
+        if self.shutdown.is_requested() {
+            return Err(TryPushError::ShutDown(item));
+        }
+        if self.is_in_flight_limit_reached() {
+            return Err(TryPushError::Full(item));
+        }
+        match self.reuse_node(item) {
+            Ok(node) => Ok(node),
+            Err(item) => {
+                if self.pool_size.is_some() {
+                    return Err(TryPushError::Exhausted(item));
+                }
+                Ok(self.allocate_node(item))
+            }
         }
-        // Allocate a new node
+    }
+
+    fn allocate_node(&mut self, item: T) -> Node<T> {
+        self.stats.record_allocated();
         Node::new(item)
     }
 
+    /// Reuse an existing node or hand back the item
+    fn reuse_node(&mut self, item: T) -> Result<Node<T>, T> {
+        let Some((mut node, _)) = self.pop_reusable_node() else {
+            return Err(item);
+        };
+        let old_item = core::mem::replace(&mut *node, item);
+        self.recycler.dispose(old_item);
+        Ok(node)
+    }
+
+    /// Pop a node for reuse with a recycled item
+    fn pop_recycled_node(&mut self) -> Option<Node<T>> {
+        let (mut node, recycled) = self.pop_reusable_node()?;
+        if !recycled {
+            self.recycler.recycle(&mut *node);
+        }
+        Some(node)
+    }
+
+    /// Pop a recycled or returned node with at least `min_capacity`
+    ///
+    /// See [`Self::push_with_capacity()`] for the fallbacks.
+    fn pop_recycled_node_with_capacity(&mut self, min_capacity: usize) -> Option<Node<T>> {
+        if let Some(node) = self.recycled_nodes.pop_with_capacity(min_capacity) {
+            self.stats.record_reused_recycled();
+            return Some(node);
+        }
+        let mut reused_node = None;
+        let mut recycled_count = 0;
+        while let Some(mut node) = self.rx.pop() {
+            self.in_flight -= 1;
+            self.recycler.recycle(&mut *node);
+            let has_capacity = self
+                .recycler
+                .capacity(&node)
+                .is_some_and(|capacity| capacity >= min_capacity);
+            if has_capacity || self.recycled_nodes.len() >= self.recycling_capacity {
+                self.stats.record_reused_returned();
+                reused_node = Some(node);
+                break;
+            }
+            self.push_recycled_node(node);
+            recycled_count += 1;
+        }
+        self.stats.record_recycled(recycled_count);
+        self.enforce_recycling_budget();
+        if reused_node.is_some() {
+            return reused_node;
+        }
+        let node = self.recycled_nodes.pop_largest()?;
+        self.stats.record_reused_recycled();
+        Some(node)
+    }
+
+    fn push_recycled_node(&mut self, node: Node<T>) {
+        let capacity = self.recycler.capacity(&node);
+        let heap_size = self.recycler.heap_size(&node);
+        self.recycled_nodes.push(node, capacity, heap_size);
+    }
+
+    /// Pop a node for reuse
+    ///
+    /// Also returns if the item of the node has already been recycled.
+    fn pop_reusable_node(&mut self) -> Option<(Node<T>, bool)> {
+        // Reuse the recycled nodes first, because this does not involve any memory barriers.
+        if let Some(node) = self.recycled_nodes.pop() {
+            self.stats.record_reused_recycled();
+            return Some((node, true));
+        }
+        let node = self.rx.pop()?;
+        self.in_flight -= 1;
+        self.stats.record_reused_returned();
+        Some((node, false))
+    }
+
+    /// Discard a node and dispose its item
+    fn drop_node(&mut self, node: Node<T>) {
+        self.stats.record_dropped();
+        let item = Node::into_inner(node);
+        self.recycler.dispose(item);
+    }
+
+    /// The number of returned nodes that have been recycled
+    #[must_use]
+    pub fn recycled_count(&self) -> usize {
+        self.stats.recycled()
+    }
+
+    /// The number of nodes that have been dropped
+    ///
+    /// Returned nodes are dropped when exceeding the recycling capacity.
+    /// Also includes recycled nodes that are evicted later when lowering
+    /// the recycling capacity or when exceeding the recycling budget,
+    /// i.e. these nodes are counted both as recycled and as dropped.
+    #[must_use]
+    pub fn dropped_count(&self) -> usize {
+        self.stats.dropped()
+    }
+
+    /// Statistics of the queue
+    #[must_use]
+    pub fn stats(&self) -> &Stats {
+        &self.stats
+    }
+
+    /// Shared handle of the statistics
+    ///
+    /// Allows to read the statistics from another thread, e.g. for
+    /// monitoring. Remains valid after the queue has been dropped.
+    #[must_use]
+    pub fn stats_handle(&self) -> Arc<Stats> {
+        Arc::clone(&self.stats)
+    }
+
+    /// Check if the consumer still exists
+    ///
+    /// The consumer might be dropped concurrently, i.e. the result
+    /// could be outdated immediately.
+    #[must_use]
+    pub fn is_consumer_alive(&self) -> bool {
+        !self.shutdown.is_consumer_dropped()
+    }
+
+    /// Request the consumer to shut down
+    ///
+    /// The consumer is supposed to return all pending items by invoking
+    /// [`Consumer::acknowledge_shutdown()`]. Afterwards all remaining
+    /// items could be collected by [`Self::drain_remaining()`].
+    ///
+    /// Only [`Self::push()`] and its variants that are not fallible still
+    /// push new items after requesting a shutdown.
+    pub fn shutdown(&mut self) {
+        self.shutdown.request();
+        self.notify_consumer();
+    }
+
+    /// Check if the consumer has acknowledged the shutdown
+    ///
+    /// Also returns `true` if the consumer has been dropped, because
+    /// it returns all pending items when dropped.
+    #[must_use]
+    pub fn is_shutdown_acknowledged(&self) -> bool {
+        self.shutdown.is_acknowledged() || !self.is_consumer_alive()
+    }
+
+    /// Collect all remaining items
+    ///
+    /// Takes both the returned and the recycled items and appends them
+    /// to `items`. In contrast to [`Self::drain_and_recycle()`] no items
+    /// are dropped, e.g. for releasing resources in an orderly manner.
+    ///
+    /// Intended to be invoked after [`Self::is_shutdown_acknowledged()`]
+    /// has been confirmed. Otherwise items that are pending or held by
+    /// the consumer will be missed.
+    ///
+    /// Returns the number of collected items.
+    pub fn drain_remaining(&mut self, items: &mut impl Extend<T>) -> usize {
+        let mut count = self.recycled_nodes.len();
+        let Self {
+            rx,
+            recycled_nodes,
+            in_flight,
+            ..
+        } = self;
+        items.extend(recycled_nodes.drain().map(Node::into_inner));
+        items.extend(core::iter::from_fn(|| {
+            let node = rx.pop()?;
+            *in_flight -= 1;
+            count += 1;
+            Some(Node::into_inner(node))
+        }));
+        count
+    }
+
+    /// The number of items in flight
+    ///
+    /// Counts all pushed items until they have been received back from
+    /// the consumer, e.g. by [`Self::drain_and_recycle()`]. Items that
+    /// have been handed back by the consumer but not yet received are
+    /// still in flight.
+    #[must_use]
+    pub fn in_flight(&self) -> usize {
+        self.in_flight
+    }
+
+    /// The limit of items in flight
+    #[must_use]
+    pub fn in_flight_limit(&self) -> Option<usize> {
+        self.in_flight_limit
+    }
+
+    /// Limit the number of items in flight
+    ///
+    /// Only applies to [`Self::try_push()`] and [`Self::try_push_with()`].
+    /// `None` disables the limit.
+    pub fn set_in_flight_limit(&mut self, in_flight_limit: Option<usize>) {
+        self.in_flight_limit = in_flight_limit;
+    }
+
+    /// Check if the limit of items in flight has been reached
+    ///
+    /// Receives all returned items first if the limit is reached.
+    fn is_in_flight_limit_reached(&mut self) -> bool {
+        let Some(in_flight_limit) = self.in_flight_limit else {
+            return false;
+        };
+        if self.in_flight < in_flight_limit {
+            return false;
+        }
+        self.recycle_returned();
+        self.in_flight >= in_flight_limit
+    }
+
     /// Tune the recycling capacity
     ///
     /// The internal buffer will never shrink when lowering the capacity.
+    /// Excess nodes are dropped starting with the largest size class,
+    /// see [`Recycler::capacity()`].
+    ///
+    /// The capacity is never lowered below the size of a preallocated pool
+    /// in bounded-allocation mode to not drop any nodes of the pool.
     pub fn tune_recycling_capacity(&mut self, recycling_capacity: usize) {
-        if recycling_capacity < self.recycled_nodes.len() {
-            self.recycled_nodes.truncate(recycling_capacity);
-        } else {
-            self.recycled_nodes
-                .reserve(recycling_capacity - self.recycled_nodes.len());
+        let recycling_capacity = recycling_capacity.max(self.pool_size.unwrap_or(0));
+        while self.recycled_nodes.len() > recycling_capacity {
+            let Some(node) = self.recycled_nodes.pop_largest() else {
+                break;
+            };
+            self.drop_node(node);
         }
+        self.recycled_nodes.reserve(recycling_capacity);
         self.recycling_capacity = recycling_capacity;
         debug_assert!(self.recycled_nodes.len() <= self.recycling_capacity);
-        debug_assert!(self.recycled_nodes.capacity() >= self.recycling_capacity);
+    }
+
+    /// The recycling budget in bytes
+    #[must_use]
+    pub fn recycling_budget(&self) -> Option<usize> {
+        self.recycling_budget
+    }
+
+    /// Limit the heap size of all recycled items
+    ///
+    /// The heap size of items is measured by [`Recycler::heap_size()`]
+    /// after recycling them. Recycled nodes with the largest items are
+    /// dropped until the budget is met, both immediately and whenever
+    /// receiving returned items. The recycling capacity still limits the
+    /// number of recycled nodes. `None` disables the budget.
+    ///
+    /// Nodes of a preallocated pool in bounded-allocation mode are never
+    /// dropped, even if the budget is exceeded.
+    pub fn set_recycling_budget(&mut self, recycling_budget: Option<usize>) {
+        self.recycling_budget = recycling_budget;
+        self.recycled_nodes
+            .index_heap_sizes(recycling_budget.is_some());
+        self.enforce_recycling_budget();
+    }
+
+    /// The total heap size of all recycled items in bytes
+    ///
+    /// See also [`Recycler::heap_size()`].
+    #[must_use]
+    pub fn recycled_heap_size(&self) -> usize {
+        self.recycled_nodes.heap_size()
+    }
+
+    /// Drop the recycled nodes with the largest items until the recycling
+    /// budget is met
+    fn enforce_recycling_budget(&mut self) {
+        let Some(recycling_budget) = self.recycling_budget else {
+            return;
+        };
+        let pool_size = self.pool_size.unwrap_or(0);
+        while self.recycled_nodes.heap_size() > recycling_budget
+            && self.recycled_nodes.len() > pool_size
+        {
+            let Some(node) = self.recycled_nodes.pop_heaviest() else {
+                break;
+            };
+            self.drop_node(node);
+        }
     }
 
     /// Drain all consumed items and recycle as much as possible
     ///
     /// Should be invoked periodically when not pushing new items.
     pub fn drain_and_recycle(&mut self) {
+        self.recycle_returned();
+    }
+
+    /// Wait until consumed items have been returned and recycle them
+    ///
+    /// Resolves with the number of returned items. Behaves like
+    /// [`Self::drain_and_recycle()`] otherwise.
+    ///
+    /// The consumer wakes the waiting task whenever it returns items,
+    /// i.e. the [`Waker`](core::task::Waker) of the executor is invoked
+    /// in the realtime context. Only await this future if waking neither
+    /// blocks nor (de-)allocates memory, e.g. if it only sets a flag that
+    /// is polled by the executor. Otherwise invoke
+    /// [`Self::drain_and_recycle()`] periodically.
+    #[cfg(feature = "async")]
+    pub fn recycled(&mut self) -> Recycled<'_, T, R, B> {
+        Recycled::new(self)
+    }
+
+    /// Drain all consumed items and return how many have been drained
+    fn recycle_returned(&mut self) -> usize {
+        let mut count = 0;
+        let mut recycled_count = 0;
         while let Some(mut node) = self.rx.pop() {
+            count += 1;
+            self.in_flight -= 1;
             if self.recycled_nodes.len() < self.recycling_capacity {
                 self.recycler.recycle(&mut *node);
-                self.recycled_nodes.push(node);
+                self.push_recycled_node(node);
+                recycled_count += 1;
+            } else {
+                self.drop_node(node);
             }
         }
+        self.stats.record_recycled(recycled_count);
+        self.enforce_recycling_budget();
+        count
+    }
+
+    #[allow(clippy::unused_self)]
+    fn notify_consumer(&self) {
+        #[cfg(feature = "async")]
+        self.wakers.consumer.wake();
+    }
+}
+
+impl<T, R, B> Drop for Producer<T, R, B>
+where
+    R: Recycler<T>,
+    B: RingBackend<T>,
+{
+    fn drop(&mut self) {
+        // Dispose all recycled and returned items on the non-realtime
+        // thread. Items that are still pending or returned later are
+        // dropped together with the consumer.
+        for node in self.recycled_nodes.drain() {
+            self.recycler.dispose(Node::into_inner(node));
+        }
+        while let Some(node) = self.rx.pop() {
+            self.recycler.dispose(Node::into_inner(node));
+        }
+        self.shutdown.drop_producer();
+        // Wake a waiting consumer to let it detect the disconnect.
+        self.notify_consumer();
+    }
+}
+
+impl<T, R, B> Extend<T> for Producer<T, R, B>
+where
+    R: Recycler<T>,
+    B: RingBackend<T>,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.push_iter(iter);
     }
 }
 
@@ -114,18 +840,93 @@ where
 ///
 /// Should be handed back to the [`Consumer`] for recycling,
 /// i.e. to keep it circling and avoid (de-)allocations.
+///
+/// Dropping an item deallocates its node, which is not realtime-safe.
+/// With the `drop-check` feature enabled dropping an item panics in
+/// debug builds. Use [`Self::into_inner()`] for intentionally discarding
+/// the node outside of a realtime context.
+#[must_use = "consumable items should be handed back to the consumer"]
 #[allow(missing_debug_implementations)]
-pub struct ConsumableItem<T>(llq::Node<T>);
+pub struct ConsumableItem<T>(ItemNode<T>);
+
+/// The node is only taken when consuming an item that is checked when dropped
+#[cfg(all(feature = "drop-check", debug_assertions))]
+type ItemNode<T> = Option<Node<T>>;
+
+#[cfg(not(all(feature = "drop-check", debug_assertions)))]
+type ItemNode<T> = Node<T>;
+
+#[cfg(all(feature = "drop-check", debug_assertions))]
+impl<T> ConsumableItem<T> {
+    const fn new(node: Node<T>) -> Self {
+        Self(Some(node))
+    }
+
+    fn into_node(mut self) -> Node<T> {
+        self.0.take().expect("node")
+    }
+
+    fn node(&self) -> &Node<T> {
+        self.0.as_ref().expect("node")
+    }
+
+    fn node_mut(&mut self) -> &mut Node<T> {
+        self.0.as_mut().expect("node")
+    }
+}
+
+#[cfg(not(all(feature = "drop-check", debug_assertions)))]
+impl<T> ConsumableItem<T> {
+    const fn new(node: Node<T>) -> Self {
+        Self(node)
+    }
+
+    fn into_node(self) -> Node<T> {
+        self.0
+    }
+
+    const fn node(&self) -> &Node<T> {
+        &self.0
+    }
+
+    fn node_mut(&mut self) -> &mut Node<T> {
+        &mut self.0
+    }
+}
+
+impl<T> ConsumableItem<T> {
+    /// Take the item and deallocate the node
+    ///
+    /// Not realtime-safe!
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        Node::into_inner(self.into_node())
+    }
+}
+
+#[cfg(all(feature = "drop-check", debug_assertions))]
+impl<T> Drop for ConsumableItem<T> {
+    fn drop(&mut self) {
+        #[cfg(feature = "std")]
+        if std::thread::panicking() {
+            return;
+        }
+        assert!(
+            self.0.is_none(),
+            "consumable item dropped instead of handing it back to the consumer"
+        );
+    }
+}
 
 impl<T> AsRef<T> for ConsumableItem<T> {
     fn as_ref(&self) -> &T {
-        &self.0
+        self.node()
     }
 }
 
 impl<T> AsMut<T> for ConsumableItem<T> {
     fn as_mut(&mut self) -> &mut T {
-        &mut self.0
+        self.node_mut()
     }
 }
 
@@ -145,27 +946,175 @@ impl<T> DerefMut for ConsumableItem<T> {
 
 /// Realtime consumer
 #[allow(missing_debug_implementations)]
-pub struct Consumer<T> {
-    rx: llq::Consumer<T>,
-    tx: llq::Producer<T>,
+pub struct Consumer<T, B = LinkedList>
+where
+    B: RingBackend<T>,
+{
+    rx: B::Rx,
+    tx: B::Tx,
+    stats: Arc<Stats>,
+    shutdown: Arc<Shutdown>,
+    #[cfg(feature = "async")]
+    wakers: Arc<Wakers>,
 }
 
-impl<T> Consumer<T> {
+impl<T, B> Consumer<T, B>
+where
+    B: RingBackend<T>,
+{
     /// Pop the next item from the queue
+    #[must_use]
     pub fn pop(&mut self) -> Option<ConsumableItem<T>> {
-        self.rx.pop().map(ConsumableItem)
+        let node = self.rx.pop()?;
+        self.stats.record_popped(1);
+        Some(ConsumableItem::new(node))
+    }
+
+    /// Pop the next item from a separate queue that shares the return path
+    pub(crate) fn pop_via(&mut self, rx: &mut B::Rx) -> Option<ConsumableItem<T>> {
+        let node = rx.pop()?;
+        self.stats.record_popped(1);
+        Some(ConsumableItem::new(node))
+    }
+
+    /// Push all pending items of a separate queue back to the producer
+    pub(crate) fn drain_via(&mut self, rx: &mut B::Rx) {
+        let count = forward_nodes(rx, &mut self.tx);
+        self.stats.record_popped(count);
+        self.stats.record_returned(count);
+        self.notify_producer();
+    }
+
+    /// Statistics of the queue
+    #[must_use]
+    pub fn stats(&self) -> &Stats {
+        &self.stats
+    }
+
+    /// Shared handle of the statistics
+    ///
+    /// See also: [`Producer::stats_handle()`]
+    #[must_use]
+    pub fn stats_handle(&self) -> Arc<Stats> {
+        Arc::clone(&self.stats)
+    }
+
+    /// Check if the producer has requested to shut down
+    ///
+    /// See also: [`Producer::shutdown()`]
+    #[must_use]
+    pub fn is_shutdown_requested(&self) -> bool {
+        self.shutdown.is_requested()
+    }
+
+    /// Acknowledge the shutdown by returning all pending items to the producer
+    ///
+    /// Items that are currently held by the realtime context must be
+    /// pushed back before.
+    pub fn acknowledge_shutdown(&mut self) {
+        self.drain();
+        self.shutdown.acknowledge();
+        self.notify_producer();
+    }
+
+    /// Check if the producer still exists
+    ///
+    /// The producer might be dropped concurrently, i.e. the result
+    /// could be outdated immediately.
+    #[must_use]
+    pub fn is_producer_alive(&self) -> bool {
+        !self.shutdown.is_producer_dropped()
     }
 
     /// Push an item back to the producer for recycling
     pub fn push_back(&mut self, consumed_item: ConsumableItem<T>) {
-        let ConsumableItem(node) = consumed_item;
-        self.tx.push(node);
+        self.tx.push(consumed_item.into_node());
+        self.stats.record_returned(1);
+        self.notify_producer();
+    }
+
+    /// Pop up to `max_count` items from the queue
+    ///
+    /// Equivalent to invoking [`Self::pop()`] repeatedly. The items are
+    /// appended to `items`, which should have enough spare capacity to
+    /// avoid (re-)allocations in a realtime context.
+    ///
+    /// Returns the number of popped items.
+    pub fn pop_batch(
+        &mut self,
+        items: &mut impl Extend<ConsumableItem<T>>,
+        max_count: usize,
+    ) -> usize {
+        let mut count = 0;
+        items.extend(
+            core::iter::from_fn(|| {
+                let node = self.rx.pop()?;
+                count += 1;
+                Some(ConsumableItem::new(node))
+            })
+            .take(max_count),
+        );
+        self.stats.record_popped(count);
+        count
+    }
+
+    /// Push multiple items back to the producer for recycling
+    ///
+    /// Equivalent to invoking [`Self::push_back()`] for each item. A producer
+    /// waiting asynchronously is only woken after all items have been pushed
+    /// back.
+    pub fn push_back_all(&mut self, consumed_items: impl IntoIterator<Item = ConsumableItem<T>>) {
+        let mut count = 0;
+        for consumed_item in consumed_items {
+            self.tx.push(consumed_item.into_node());
+            count += 1;
+        }
+        self.stats.record_returned(count);
+        self.notify_producer();
     }
 
     /// Consume all pending items by pushing them back to the producer
     pub fn drain(&mut self) {
-        while let Some(node) = self.rx.pop() {
-            self.tx.push(node);
-        }
+        let count = forward_nodes(&mut self.rx, &mut self.tx);
+        self.stats.record_popped(count);
+        self.stats.record_returned(count);
+        self.notify_producer();
+    }
+
+    #[allow(clippy::unused_self)]
+    fn notify_producer(&self) {
+        #[cfg(feature = "async")]
+        self.wakers.producer.wake();
+    }
+
+    /// Asynchronously receive items as a [`Stream`](futures_core::Stream)
+    ///
+    /// Intended for consumers that run outside of a realtime context.
+    #[cfg(feature = "async")]
+    pub fn stream(&mut self) -> ConsumerStream<'_, T, B> {
+        ConsumerStream::new(self)
+    }
+}
+
+impl<T, B> Drop for Consumer<T, B>
+where
+    B: RingBackend<T>,
+{
+    fn drop(&mut self) {
+        // Return all pending nodes to not deallocate them in the realtime
+        // context. Only if the producer has already been dropped, all
+        // remaining nodes are deallocated here.
+        self.drain();
+        self.shutdown.drop_consumer();
+    }
+}
+
+/// Forward all pending nodes and return how many have been forwarded
+fn forward_nodes<T>(rx: &mut impl NodeRx<T>, tx: &mut impl NodeTx<T>) -> usize {
+    let mut count = 0;
+    while let Some(node) = rx.pop() {
+        tx.push(node);
+        count += 1;
     }
+    count
 }
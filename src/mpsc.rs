@@ -0,0 +1,121 @@
+// SPDX-FileCopyrightText: The cirque authors
+// SPDX-License-Identifier: MPL-2.0
+
+//! Multiple producers feeding a single realtime consumer
+//!
+//! All producer handles share a single [`Producer`] and [`Recycler`].
+//! Access is synchronized by a lock on the non-realtime side, i.e. the
+//! [`Consumer`] is not affected.
+
+use std::sync::PoisonError;
+
+use crate::{
+    sync::{Arc, Mutex, MutexGuard},
+    Consumer, Producer, Recycler, StatsSnapshot, TryPushError,
+};
+
+/// Create a new multi-producer/consumer circular queue.
+#[must_use]
+pub fn new_producer_consumer<T, R>(
+    recycler: R,
+    recycling_capacity: usize,
+) -> (MpscProducer<T, R>, Consumer<T>)
+where
+    R: Recycler<T>,
+{
+    let (producer, consumer) = crate::new_producer_consumer(recycler, recycling_capacity);
+    (producer.into(), consumer)
+}
+
+/// Cloneable, non-realtime producer handle
+#[allow(missing_debug_implementations)]
+pub struct MpscProducer<T, R>(Arc<Mutex<Producer<T, R>>>)
+where
+    R: Recycler<T>;
+
+impl<T, R> Clone for MpscProducer<T, R>
+where
+    R: Recycler<T>,
+{
+    fn clone(&self) -> Self {
+        let Self(producer) = self;
+        Self(Arc::clone(producer))
+    }
+}
+
+impl<T, R> From<Producer<T, R>> for MpscProducer<T, R>
+where
+    R: Recycler<T>,
+{
+    fn from(producer: Producer<T, R>) -> Self {
+        Self(Arc::new(Mutex::new(producer)))
+    }
+}
+
+impl<T, R> MpscProducer<T, R>
+where
+    R: Recycler<T>,
+{
+    fn lock(&self) -> MutexGuard<'_, Producer<T, R>> {
+        // The producer remains consistent even if a recycler has panicked.
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Push a new item into the queue
+    ///
+    /// See also: [`Producer::push()`]
+    pub fn push(&self, item: T) {
+        self.lock().push(item);
+    }
+
+    /// Try to push a new item into the queue
+    ///
+    /// See also: [`Producer::try_push()`]
+    ///
+    /// # Errors
+    ///
+    /// Returns the rejected item.
+    pub fn try_push(&self, item: T) -> Result<(), TryPushError<T>> {
+        self.lock().try_push(item)
+    }
+
+    /// Push a new item into the queue by filling it in-place
+    ///
+    /// See also: [`Producer::push_with()`]
+    pub fn push_with(&self, fill: impl FnOnce(&mut T))
+    where
+        T: Default,
+    {
+        self.lock().push_with(fill);
+    }
+
+    /// Push multiple new items into the queue
+    ///
+    /// The items are pushed consecutively without interleaving
+    /// with other producers.
+    ///
+    /// See also: [`Producer::push_iter()`]
+    pub fn push_iter(&self, items: impl IntoIterator<Item = T>) {
+        self.lock().push_iter(items);
+    }
+
+    /// Drain all consumed items and recycle as much as possible
+    ///
+    /// See also: [`Producer::drain_and_recycle()`]
+    pub fn drain_and_recycle(&self) {
+        self.lock().drain_and_recycle();
+    }
+
+    /// Tune the recycling capacity
+    ///
+    /// See also: [`Producer::tune_recycling_capacity()`]
+    pub fn tune_recycling_capacity(&self, recycling_capacity: usize) {
+        self.lock().tune_recycling_capacity(recycling_capacity);
+    }
+
+    /// Snapshot of the statistics of the queue
+    #[must_use]
+    pub fn stats(&self) -> StatsSnapshot {
+        self.lock().stats().snapshot()
+    }
+}
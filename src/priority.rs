@@ -0,0 +1,185 @@
+// SPDX-FileCopyrightText: The cirque authors
+// SPDX-License-Identifier: MPL-2.0
+
+//! Priority lanes for a single realtime consumer
+//!
+//! Items are pushed into one of multiple lanes. The consumer always drains
+//! the lanes in order of their priority, i.e. urgent items are not delayed
+//! by a burst of bulk items. All lanes share a single return path for
+//! recycling.
+//!
+//! Lanes are identified by their index. The lane with index 0 has the
+//! highest priority.
+
+use alloc::vec::Vec;
+
+use crate::{
+    backend::{LinkedList, LinkedListRx, LinkedListTx, RingBackend as _},
+    ConsumableItem, Consumer, Node, Producer, Recycler, Stats, TryPushError,
+};
+
+/// Create a new producer/consumer circular queue with multiple priority lanes.
+///
+/// # Panics
+///
+/// Panics if `lane_count` is 0.
+#[must_use]
+pub fn new_producer_consumer<T, R>(
+    recycler: R,
+    recycling_capacity: usize,
+    lane_count: usize,
+) -> (PriorityProducer<T, R>, PriorityConsumer<T>)
+where
+    R: Recycler<T>,
+{
+    assert!(lane_count > 0, "at least one lane is required");
+    let (producer, consumer) = crate::new_producer_consumer(recycler, recycling_capacity);
+    // The first lane is provided by the queue of the producer/consumer.
+    let (lanes_tx, lanes_rx) = (1..lane_count).map(|_| LinkedList.new_queue()).unzip();
+    let producer = PriorityProducer {
+        producer,
+        lanes: lanes_tx,
+    };
+    let consumer = PriorityConsumer {
+        consumer,
+        lanes: lanes_rx,
+    };
+    (producer, consumer)
+}
+
+/// Non-realtime producer with priority lanes
+#[allow(missing_debug_implementations)]
+pub struct PriorityProducer<T, R>
+where
+    R: Recycler<T>,
+{
+    producer: Producer<T, R>,
+    lanes: Vec<LinkedListTx<T>>,
+}
+
+impl<T, R> PriorityProducer<T, R>
+where
+    R: Recycler<T>,
+{
+    /// The number of lanes
+    #[must_use]
+    pub fn lane_count(&self) -> usize {
+        1 + self.lanes.len()
+    }
+
+    /// Push a new item into the given lane
+    ///
+    /// See also: [`Producer::push()`]
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lane` is out of range.
+    pub fn push(&mut self, lane: usize, item: T) {
+        let node = self.producer.new_node(item);
+        self.push_node(lane, node);
+    }
+
+    /// Try to push a new item into the given lane
+    ///
+    /// See also: [`Producer::try_push()`]
+    ///
+    /// # Errors
+    ///
+    /// Returns the rejected item.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lane` is out of range.
+    pub fn try_push(&mut self, lane: usize, item: T) -> Result<(), TryPushError<T>> {
+        let node = self.producer.try_new_node(item)?;
+        self.push_node(lane, node);
+        Ok(())
+    }
+
+    fn push_node(&mut self, lane: usize, node: Node<T>) {
+        if lane == 0 {
+            self.producer.push_node(node);
+        } else {
+            let tx = &mut self.lanes[lane - 1];
+            self.producer.push_node_via(tx, node);
+        }
+    }
+
+    /// Drain all consumed items and recycle as much as possible
+    ///
+    /// See also: [`Producer::drain_and_recycle()`]
+    pub fn drain_and_recycle(&mut self) {
+        self.producer.drain_and_recycle();
+    }
+
+    /// Tune the recycling capacity
+    ///
+    /// See also: [`Producer::tune_recycling_capacity()`]
+    pub fn tune_recycling_capacity(&mut self, recycling_capacity: usize) {
+        self.producer.tune_recycling_capacity(recycling_capacity);
+    }
+
+    /// Statistics of all lanes
+    #[must_use]
+    pub fn stats(&self) -> &Stats {
+        self.producer.stats()
+    }
+}
+
+/// Realtime consumer with priority lanes
+#[allow(missing_debug_implementations)]
+pub struct PriorityConsumer<T> {
+    consumer: Consumer<T>,
+    lanes: Vec<LinkedListRx<T>>,
+}
+
+impl<T> PriorityConsumer<T> {
+    /// The number of lanes
+    #[must_use]
+    pub fn lane_count(&self) -> usize {
+        1 + self.lanes.len()
+    }
+
+    /// Pop the next item from the lane with the highest priority
+    #[must_use]
+    pub fn pop(&mut self) -> Option<ConsumableItem<T>> {
+        if let Some(item) = self.consumer.pop() {
+            return Some(item);
+        }
+        let Self { consumer, lanes } = self;
+        lanes.iter_mut().find_map(|rx| consumer.pop_via(rx))
+    }
+
+    /// Push an item back to the producer for recycling
+    ///
+    /// See also: [`Consumer::push_back()`]
+    pub fn push_back(&mut self, consumed_item: ConsumableItem<T>) {
+        self.consumer.push_back(consumed_item);
+    }
+
+    /// Consume all pending items of all lanes by pushing them back to the producer
+    pub fn drain(&mut self) {
+        self.consumer.drain();
+        let Self { consumer, lanes } = self;
+        for rx in lanes {
+            consumer.drain_via(rx);
+        }
+    }
+
+    /// Statistics of all lanes
+    #[must_use]
+    pub fn stats(&self) -> &Stats {
+        self.consumer.stats()
+    }
+}
+
+impl<T> Drop for PriorityConsumer<T> {
+    fn drop(&mut self) {
+        // Return all pending nodes of the additional lanes like the
+        // consumer does for the first lane.
+        let Self { consumer, lanes } = self;
+        for rx in lanes {
+            consumer.drain_via(rx);
+        }
+    }
+}
@@ -0,0 +1,224 @@
+// SPDX-FileCopyrightText: The cirque authors
+// SPDX-License-Identifier: MPL-2.0
+
+//! Ready-made recyclers for common types
+//!
+//! Closures that accept a mutable reference to an item could be used
+//! as a [`Recycler`] as well.
+//!
+//! Wrap a recycler into a [`SizeClassRecycler`] for picking recycled
+//! items by their capacity with
+//! [`Producer::push_with_capacity()`](crate::Producer::push_with_capacity).
+//! Wrap it into a [`HeapSizeRecycler`] for measuring the heap size of
+//! items that is accounted for the
+//! [recycling budget](crate::Producer::set_recycling_budget).
+//! The recyclers for buffers already measure the heap size of the buffers.
+
+use alloc::{collections::VecDeque, string::String, vec::Vec};
+use core::mem::size_of;
+
+use crate::Recycler;
+
+impl<T, F> Recycler<T> for F
+where
+    F: FnMut(&mut T),
+{
+    fn recycle(&mut self, item: &mut T) {
+        self(item);
+    }
+}
+
+/// Leaves all items untouched
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NoOpRecycler;
+
+impl<T> Recycler<T> for NoOpRecycler {
+    fn recycle(&mut self, _item: &mut T) {}
+}
+
+/// Clears buffers while retaining their capacity and resets options to `None`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ClearRecycler;
+
+impl<T> Recycler<Vec<T>> for ClearRecycler {
+    fn recycle(&mut self, item: &mut Vec<T>) {
+        item.clear();
+    }
+
+    fn heap_size(&self, item: &Vec<T>) -> usize {
+        item.capacity() * size_of::<T>()
+    }
+}
+
+impl<T> Recycler<VecDeque<T>> for ClearRecycler {
+    fn recycle(&mut self, item: &mut VecDeque<T>) {
+        item.clear();
+    }
+
+    fn heap_size(&self, item: &VecDeque<T>) -> usize {
+        item.capacity() * size_of::<T>()
+    }
+}
+
+impl Recycler<String> for ClearRecycler {
+    fn recycle(&mut self, item: &mut String) {
+        item.clear();
+    }
+
+    fn heap_size(&self, item: &String) -> usize {
+        item.capacity()
+    }
+}
+
+impl<T> Recycler<Option<T>> for ClearRecycler {
+    fn recycle(&mut self, item: &mut Option<T>) {
+        *item = None;
+    }
+}
+
+/// Clears buffers and shrinks oversized buffers
+///
+/// The capacity of buffers that exceed `max_capacity` is shrunk down
+/// to `max_capacity`. Smaller buffers retain their capacity like with
+/// [`ClearRecycler`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShrinkRecycler {
+    /// The maximum capacity of recycled buffers
+    pub max_capacity: usize,
+}
+
+impl ShrinkRecycler {
+    /// Create a new recycler with the given maximum capacity
+    #[must_use]
+    pub const fn new(max_capacity: usize) -> Self {
+        Self { max_capacity }
+    }
+}
+
+impl<T> Recycler<Vec<T>> for ShrinkRecycler {
+    fn recycle(&mut self, item: &mut Vec<T>) {
+        item.clear();
+        if item.capacity() > self.max_capacity {
+            item.shrink_to(self.max_capacity);
+        }
+    }
+
+    fn heap_size(&self, item: &Vec<T>) -> usize {
+        item.capacity() * size_of::<T>()
+    }
+}
+
+impl<T> Recycler<VecDeque<T>> for ShrinkRecycler {
+    fn recycle(&mut self, item: &mut VecDeque<T>) {
+        item.clear();
+        if item.capacity() > self.max_capacity {
+            item.shrink_to(self.max_capacity);
+        }
+    }
+
+    fn heap_size(&self, item: &VecDeque<T>) -> usize {
+        item.capacity() * size_of::<T>()
+    }
+}
+
+impl Recycler<String> for ShrinkRecycler {
+    fn recycle(&mut self, item: &mut String) {
+        item.clear();
+        if item.capacity() > self.max_capacity {
+            item.shrink_to(self.max_capacity);
+        }
+    }
+
+    fn heap_size(&self, item: &String) -> usize {
+        item.capacity()
+    }
+}
+
+/// Provides the capacity of items for bucketing them into size classes
+///
+/// Delegates recycling and disposal to the wrapped recycler. The capacity
+/// of an item is measured by a closure, e.g. [`Vec::capacity()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SizeClassRecycler<R, F> {
+    /// The wrapped recycler
+    pub recycler: R,
+
+    /// Measures the capacity of an item
+    pub capacity: F,
+}
+
+impl<R, F> SizeClassRecycler<R, F> {
+    /// Wrap a recycler and measure the capacity of items with a closure
+    #[must_use]
+    pub const fn new(recycler: R, capacity: F) -> Self {
+        Self { recycler, capacity }
+    }
+}
+
+impl<T, R, F> Recycler<T> for SizeClassRecycler<R, F>
+where
+    R: Recycler<T>,
+    F: Fn(&T) -> usize,
+{
+    fn recycle(&mut self, item: &mut T) {
+        self.recycler.recycle(item);
+    }
+
+    fn dispose(&mut self, item: T) {
+        self.recycler.dispose(item);
+    }
+
+    fn capacity(&self, item: &T) -> Option<usize> {
+        Some((self.capacity)(item))
+    }
+
+    fn heap_size(&self, item: &T) -> usize {
+        self.recycler.heap_size(item)
+    }
+}
+
+/// Provides the heap size of items for the recycling budget
+///
+/// Delegates recycling and disposal to the wrapped recycler. The heap
+/// size of an item in bytes is measured by a closure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeapSizeRecycler<R, F> {
+    /// The wrapped recycler
+    pub recycler: R,
+
+    /// Measures the heap size of an item
+    pub heap_size: F,
+}
+
+impl<R, F> HeapSizeRecycler<R, F> {
+    /// Wrap a recycler and measure the heap size of items with a closure
+    #[must_use]
+    pub const fn new(recycler: R, heap_size: F) -> Self {
+        Self {
+            recycler,
+            heap_size,
+        }
+    }
+}
+
+impl<T, R, F> Recycler<T> for HeapSizeRecycler<R, F>
+where
+    R: Recycler<T>,
+    F: Fn(&T) -> usize,
+{
+    fn recycle(&mut self, item: &mut T) {
+        self.recycler.recycle(item);
+    }
+
+    fn dispose(&mut self, item: T) {
+        self.recycler.dispose(item);
+    }
+
+    fn capacity(&self, item: &T) -> Option<usize> {
+        self.recycler.capacity(item)
+    }
+
+    fn heap_size(&self, item: &T) -> usize {
+        (self.heap_size)(item)
+    }
+}
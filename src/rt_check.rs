@@ -0,0 +1,191 @@
+// SPDX-FileCopyrightText: The cirque authors
+// SPDX-License-Identifier: MPL-2.0
+
+//! Verification of realtime-safety
+//!
+//! Test support for detecting (de-)allocations in a realtime context.
+//! Requires to install a [`CountingAllocator`] as the global allocator:
+//!
+//! ```
+//! use std::alloc::System;
+//!
+//! use cirque::rt_check::{assert_no_alloc, CountingAllocator};
+//!
+//! #[global_allocator]
+//! static ALLOCATOR: CountingAllocator<System> = CountingAllocator::new(System);
+//!
+//! let mut buffer = Vec::<u8>::with_capacity(1);
+//! assert_no_alloc(|| buffer.push(1));
+//! ```
+//!
+//! Only (de-)allocations on the guarded thread are counted. Allocations
+//! that are detected by the allocator are recorded instead of panicking
+//! right away, because panicking requires to allocate memory.
+
+#![allow(unsafe_code)]
+
+use std::{
+    alloc::{GlobalAlloc, Layout},
+    cell::Cell,
+    marker::PhantomData,
+};
+
+/// Counters of the current thread
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocCounts {
+    /// The number of allocations, including reallocations
+    pub allocations: usize,
+
+    /// The number of deallocations
+    pub deallocations: usize,
+}
+
+impl AllocCounts {
+    /// Check if neither allocations nor deallocations have been counted
+    #[must_use]
+    pub const fn is_zero(&self) -> bool {
+        self.allocations == 0 && self.deallocations == 0
+    }
+}
+
+thread_local! {
+    /// Nesting depth of [`Guard`]s on the current thread
+    static GUARD_DEPTH: Cell<usize> = const { Cell::new(0) };
+
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+
+    static DEALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+fn is_guarded() -> bool {
+    // Accessing the thread-local storage fails while the thread is torn down.
+    GUARD_DEPTH
+        .try_with(|depth| depth.get() > 0)
+        .unwrap_or(false)
+}
+
+fn count(counter: &'static std::thread::LocalKey<Cell<usize>>) {
+    if !is_guarded() {
+        return;
+    }
+    let _ = counter.try_with(|count| count.set(count.get() + 1));
+}
+
+fn current_counts() -> AllocCounts {
+    AllocCounts {
+        allocations: ALLOCATIONS.with(Cell::get),
+        deallocations: DEALLOCATIONS.with(Cell::get),
+    }
+}
+
+/// Global allocator that counts (de-)allocations on guarded threads
+///
+/// Delegates to the wrapped allocator.
+#[derive(Debug, Default)]
+pub struct CountingAllocator<A> {
+    inner: A,
+}
+
+impl<A> CountingAllocator<A> {
+    /// Wrap an allocator
+    #[must_use]
+    pub const fn new(inner: A) -> Self {
+        Self { inner }
+    }
+}
+
+unsafe impl<A> GlobalAlloc for CountingAllocator<A>
+where
+    A: GlobalAlloc,
+{
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count(&ALLOCATIONS);
+        self.inner.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        count(&ALLOCATIONS);
+        self.inner.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count(&ALLOCATIONS);
+        self.inner.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        count(&DEALLOCATIONS);
+        self.inner.dealloc(ptr, layout);
+    }
+}
+
+/// Counts (de-)allocations on the current thread while alive
+///
+/// Guards could be nested. Each guard only reports the (de-)allocations
+/// since it has been created.
+#[derive(Debug)]
+pub struct Guard {
+    start: AllocCounts,
+    // Bound to the current thread
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Guard {
+    /// Start counting on the current thread
+    #[must_use]
+    pub fn new() -> Self {
+        GUARD_DEPTH.with(|depth| depth.set(depth.get() + 1));
+        Self {
+            start: current_counts(),
+            _not_send: PhantomData,
+        }
+    }
+
+    /// The (de-)allocations that have been counted since the guard has been created
+    #[must_use]
+    pub fn counts(&self) -> AllocCounts {
+        let AllocCounts {
+            allocations,
+            deallocations,
+        } = current_counts();
+        AllocCounts {
+            allocations: allocations - self.start.allocations,
+            deallocations: deallocations - self.start.deallocations,
+        }
+    }
+}
+
+impl Default for Guard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        GUARD_DEPTH.with(|depth| depth.set(depth.get() - 1));
+    }
+}
+
+/// Invoke a function and return the (de-)allocations it has caused
+pub fn count_alloc<R>(f: impl FnOnce() -> R) -> (R, AllocCounts) {
+    let guard = Guard::new();
+    let result = f();
+    let counts = guard.counts();
+    (result, counts)
+}
+
+/// Invoke a function and assert that it neither allocates nor deallocates memory
+///
+/// # Panics
+///
+/// Panics if any (de-)allocations have been counted.
+#[track_caller]
+pub fn assert_no_alloc<R>(f: impl FnOnce() -> R) -> R {
+    let (result, counts) = count_alloc(f);
+    assert!(
+        counts.is_zero(),
+        "unexpected (de-)allocations in realtime context: {counts:?}"
+    );
+    result
+}
@@ -0,0 +1,240 @@
+// SPDX-FileCopyrightText: The cirque authors
+// SPDX-License-Identifier: MPL-2.0
+
+//! Timestamped scheduling
+//!
+//! Items are tagged with a time, e.g. a frame or sample position, and
+//! only delivered to the realtime consumer once this time is due.
+//!
+//! Items that are not yet due are kept pending in a reorder buffer on
+//! the consumer side. The buffer is preallocated with a fixed capacity
+//! and never grows, i.e. it doesn't (re-)allocate any memory in the
+//! realtime context.
+//!
+//! While the buffer is full the next item is held back in a single
+//! overflow slot and all following items remain in the queue. Items in
+//! the queue might be due earlier than the buffered items and are then
+//! delivered out of order, see [`ScheduledConsumer::is_overflowing()`].
+
+use alloc::collections::VecDeque;
+
+use crate::{ConsumableItem, Consumer, Producer, Recycler, Stats};
+
+/// Time of an item, e.g. a frame or sample position
+pub type Time = u64;
+
+/// Item that is tagged with the time when it is due
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Scheduled<T> {
+    /// The time when the item is due
+    pub time: Time,
+
+    /// The actual item
+    pub item: T,
+}
+
+/// Adapts a [`Recycler`] for the items to [`Scheduled`] items
+#[derive(Debug)]
+struct ScheduledRecycler<R>(R);
+
+impl<T, R> Recycler<Scheduled<T>> for ScheduledRecycler<R>
+where
+    R: Recycler<T>,
+{
+    fn recycle(&mut self, item: &mut Scheduled<T>) {
+        self.0.recycle(&mut item.item);
+    }
+
+    fn dispose(&mut self, item: Scheduled<T>) {
+        self.0.dispose(item.item);
+    }
+
+    fn capacity(&self, item: &Scheduled<T>) -> Option<usize> {
+        self.0.capacity(&item.item)
+    }
+
+    fn heap_size(&self, item: &Scheduled<T>) -> usize {
+        self.0.heap_size(&item.item)
+    }
+}
+
+/// Create a new producer/consumer circular queue for scheduled items.
+///
+/// The consumer keeps up to `reorder_capacity` pending items that are
+/// not yet due.
+///
+/// # Panics
+///
+/// Panics if `reorder_capacity` is 0.
+#[must_use]
+pub fn new_producer_consumer<T, R>(
+    recycler: R,
+    recycling_capacity: usize,
+    reorder_capacity: usize,
+) -> (ScheduledProducer<T, R>, ScheduledConsumer<T>)
+where
+    R: Recycler<T>,
+{
+    assert!(reorder_capacity > 0, "reorder capacity must not be 0");
+    let (producer, consumer) =
+        crate::new_producer_consumer(ScheduledRecycler(recycler), recycling_capacity);
+    let producer = ScheduledProducer { producer };
+    let consumer = ScheduledConsumer {
+        consumer,
+        pending: VecDeque::with_capacity(reorder_capacity),
+        reorder_capacity,
+        overflow: None,
+    };
+    (producer, consumer)
+}
+
+/// Non-realtime producer of scheduled items
+#[allow(missing_debug_implementations)]
+pub struct ScheduledProducer<T, R>
+where
+    R: Recycler<T>,
+{
+    producer: Producer<Scheduled<T>, ScheduledRecycler<R>>,
+}
+
+impl<T, R> ScheduledProducer<T, R>
+where
+    R: Recycler<T>,
+{
+    /// Push a new item into the queue that is due at the given time
+    ///
+    /// See also: [`Producer::push()`]
+    pub fn push_at(&mut self, time: Time, item: T) {
+        self.producer.push(Scheduled { time, item });
+    }
+
+    /// Drain all consumed items and recycle as much as possible
+    ///
+    /// See also: [`Producer::drain_and_recycle()`]
+    pub fn drain_and_recycle(&mut self) {
+        self.producer.drain_and_recycle();
+    }
+
+    /// Tune the recycling capacity
+    ///
+    /// See also: [`Producer::tune_recycling_capacity()`]
+    pub fn tune_recycling_capacity(&mut self, recycling_capacity: usize) {
+        self.producer.tune_recycling_capacity(recycling_capacity);
+    }
+
+    /// Statistics of the queue
+    #[must_use]
+    pub fn stats(&self) -> &Stats {
+        self.producer.stats()
+    }
+}
+
+/// Realtime consumer of scheduled items
+#[allow(missing_debug_implementations)]
+pub struct ScheduledConsumer<T> {
+    consumer: Consumer<Scheduled<T>>,
+    /// Pending items ordered by their time
+    pending: VecDeque<ConsumableItem<Scheduled<T>>>,
+    /// The fixed capacity of `pending` that must never be exceeded
+    reorder_capacity: usize,
+    /// The next item from the queue that didn't fit into `pending`
+    overflow: Option<ConsumableItem<Scheduled<T>>>,
+}
+
+impl<T> ScheduledConsumer<T> {
+    /// Pop the next item that is due at `now`
+    ///
+    /// Items are delivered in order of their time. Items with the same
+    /// time are delivered in the order they have been pushed.
+    ///
+    /// The order is only guaranteed as long as the reorder buffer is not
+    /// overflowing. Otherwise items that are still in the queue are not
+    /// considered, even if they are due earlier.
+    #[must_use]
+    pub fn pop_due(&mut self, now: Time) -> Option<ConsumableItem<Scheduled<T>>> {
+        self.fill_pending();
+        let front_time = self.pending.front()?.time;
+        // The overflow item has been pushed after all buffered items.
+        if let Some(overflow_time) = self.overflow.as_ref().map(|item| item.time) {
+            if overflow_time < front_time {
+                if overflow_time > now {
+                    return None;
+                }
+                return self.overflow.take();
+            }
+        }
+        if front_time > now {
+            return None;
+        }
+        self.pending.pop_front()
+    }
+
+    /// Check if the reorder buffer is overflowing
+    ///
+    /// The buffer is full and at least one more item has been received.
+    /// Items that are still in the queue might be due earlier than the
+    /// items that are delivered next, i.e. the time order of
+    /// [`Self::pop_due()`] is no longer guaranteed. Updated when popping
+    /// items.
+    #[must_use]
+    pub fn is_overflowing(&self) -> bool {
+        self.overflow.is_some()
+    }
+
+    /// The number of pending items in the reorder buffer
+    ///
+    /// Doesn't include items that are still in the queue.
+    #[must_use]
+    pub fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Push an item back to the producer for recycling
+    ///
+    /// See also: [`Consumer::push_back()`]
+    pub fn push_back(&mut self, consumed_item: ConsumableItem<Scheduled<T>>) {
+        self.consumer.push_back(consumed_item);
+    }
+
+    /// Consume all pending items, even if not yet due, by pushing them
+    /// back to the producer
+    pub fn drain(&mut self) {
+        self.consumer.push_back_all(self.pending.drain(..));
+        self.consumer.push_back_all(self.overflow.take());
+        self.consumer.drain();
+    }
+
+    /// Statistics of the queue
+    #[must_use]
+    pub fn stats(&self) -> &Stats {
+        self.consumer.stats()
+    }
+
+    /// Move items from the queue into the reorder buffer while there is room
+    ///
+    /// Holds back the next item in the overflow slot if the buffer is full.
+    fn fill_pending(&mut self) {
+        while self.pending.len() < self.reorder_capacity {
+            let Some(item) = self.overflow.take().or_else(|| self.consumer.pop()) else {
+                return;
+            };
+            // Insert after all items with the same time to retain the push order.
+            let index = self
+                .pending
+                .partition_point(|pending| pending.time <= item.time);
+            self.pending.insert(index, item);
+        }
+        debug_assert!(self.pending.capacity() >= self.reorder_capacity);
+        if self.overflow.is_none() {
+            self.overflow = self.consumer.pop();
+        }
+    }
+}
+
+impl<T> Drop for ScheduledConsumer<T> {
+    fn drop(&mut self) {
+        // Return all pending items instead of dropping them.
+        self.consumer.push_back_all(self.pending.drain(..));
+        self.consumer.push_back_all(self.overflow.take());
+    }
+}
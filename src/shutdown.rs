@@ -0,0 +1,56 @@
+// SPDX-FileCopyrightText: The cirque authors
+// SPDX-License-Identifier: MPL-2.0
+
+//! Graceful shutdown and disconnection
+
+use crate::sync::{AtomicBool, Ordering};
+
+/// Shutdown state shared by the [`Producer`](crate::Producer) and the [`Consumer`](crate::Consumer)
+#[derive(Debug, Default)]
+pub(crate) struct Shutdown {
+    /// Set by the producer
+    requested: AtomicBool,
+
+    /// Set by the consumer after all pending items have been returned
+    acknowledged: AtomicBool,
+
+    /// Set by the producer when dropped
+    producer_dropped: AtomicBool,
+
+    /// Set by the consumer when dropped after all pending items have been returned
+    consumer_dropped: AtomicBool,
+}
+
+impl Shutdown {
+    pub(crate) fn request(&self) {
+        self.requested.store(true, Ordering::Release);
+    }
+
+    pub(crate) fn is_requested(&self) -> bool {
+        self.requested.load(Ordering::Acquire)
+    }
+
+    pub(crate) fn acknowledge(&self) {
+        self.acknowledged.store(true, Ordering::Release);
+    }
+
+    pub(crate) fn is_acknowledged(&self) -> bool {
+        self.acknowledged.load(Ordering::Acquire)
+    }
+
+    pub(crate) fn drop_producer(&self) {
+        self.producer_dropped.store(true, Ordering::Release);
+    }
+
+    pub(crate) fn is_producer_dropped(&self) -> bool {
+        self.producer_dropped.load(Ordering::Acquire)
+    }
+
+    pub(crate) fn drop_consumer(&self) {
+        self.consumer_dropped.store(true, Ordering::Release);
+    }
+
+    pub(crate) fn is_consumer_dropped(&self) -> bool {
+        self.consumer_dropped.load(Ordering::Acquire)
+    }
+}
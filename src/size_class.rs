@@ -0,0 +1,267 @@
+// SPDX-FileCopyrightText: The cirque authors
+// SPDX-License-Identifier: MPL-2.0
+
+//! Recycled nodes ordered by the capacity of their items
+//!
+//! Each distinct capacity forms a size class. Nodes within the same
+//! size class are reused in LIFO order. Nodes without a capacity form
+//! the first size class. They are kept in a preallocated buffer, i.e.
+//! recycling them doesn't allocate memory.
+//!
+//! The total heap size of all items is always tracked. Nodes are only
+//! ordered by the heap size of their items while heap sizes are indexed,
+//! e.g. for enforcing a memory budget. Nodes without a capacity are then
+//! reused starting with the largest item. Popping the node with the
+//! largest item takes logarithmic time.
+
+use alloc::{
+    collections::{BTreeMap, BTreeSet, BinaryHeap},
+    vec::Vec,
+};
+use core::{
+    cmp::{Ordering, Reverse},
+    iter, mem,
+};
+
+use crate::Node;
+
+/// Orders nodes by capacity and the most recently pushed node first
+type CapacityKey = (usize, Reverse<u64>);
+
+/// Orders nodes by heap size, capacity, and the most recently pushed node last
+type HeapSizeKey = (usize, usize, u64);
+
+/// A node together with the heap size of its item
+///
+/// Ordered only by the heap size.
+struct Entry<T> {
+    heap_size: usize,
+    node: Node<T>,
+}
+
+impl<T> PartialEq for Entry<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.heap_size == other.heap_size
+    }
+}
+
+impl<T> Eq for Entry<T> {}
+
+impl<T> PartialOrd for Entry<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Entry<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.heap_size.cmp(&other.heap_size)
+    }
+}
+
+/// Nodes without a capacity
+enum UnsizedNodes<T> {
+    /// Reused in LIFO order
+    Stack(Vec<Entry<T>>),
+
+    /// Reused starting with the largest heap size
+    Heap(BinaryHeap<Entry<T>>),
+}
+
+impl<T> UnsizedNodes<T> {
+    fn len(&self) -> usize {
+        match self {
+            Self::Stack(entries) => entries.len(),
+            Self::Heap(entries) => entries.len(),
+        }
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        match self {
+            Self::Stack(entries) => entries.reserve(additional),
+            Self::Heap(entries) => entries.reserve(additional),
+        }
+    }
+
+    fn push(&mut self, entry: Entry<T>) {
+        match self {
+            Self::Stack(entries) => entries.push(entry),
+            Self::Heap(entries) => entries.push(entry),
+        }
+    }
+
+    fn pop(&mut self) -> Option<Entry<T>> {
+        match self {
+            Self::Stack(entries) => entries.pop(),
+            Self::Heap(entries) => entries.pop(),
+        }
+    }
+
+    /// The largest heap size, only available while ordered by heap size
+    fn peek_heap_size(&self) -> Option<usize> {
+        match self {
+            Self::Stack(_) => None,
+            Self::Heap(entries) => entries.peek().map(|entry| entry.heap_size),
+        }
+    }
+
+    /// Switch between LIFO order and heap size order, retaining the buffer
+    fn order_by_heap_size(&mut self, enabled: bool) {
+        *self = match mem::replace(self, Self::Stack(Vec::new())) {
+            Self::Stack(entries) if enabled => Self::Heap(entries.into()),
+            Self::Heap(entries) if !enabled => Self::Stack(entries.into_vec()),
+            unchanged => unchanged,
+        };
+    }
+}
+
+/// Recycled nodes together with the capacity and heap size of their items
+pub(crate) struct SizeClasses<T> {
+    unsized_nodes: UnsizedNodes<T>,
+    by_capacity: BTreeMap<CapacityKey, Entry<T>>,
+    /// Only maintained while heap sizes are indexed
+    by_heap_size: Option<BTreeSet<HeapSizeKey>>,
+    /// Sequence number of the next pushed node
+    next_seq: u64,
+    heap_size: usize,
+}
+
+impl<T> SizeClasses<T> {
+    /// Reserves `capacity` for nodes without a capacity
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        Self {
+            unsized_nodes: UnsizedNodes::Stack(Vec::with_capacity(capacity)),
+            by_capacity: BTreeMap::new(),
+            by_heap_size: None,
+            next_seq: 0,
+            heap_size: 0,
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.unsized_nodes.len() + self.by_capacity.len()
+    }
+
+    /// The total heap size of all items
+    pub(crate) const fn heap_size(&self) -> usize {
+        self.heap_size
+    }
+
+    pub(crate) fn reserve(&mut self, capacity: usize) {
+        let nodes = &mut self.unsized_nodes;
+        nodes.reserve(capacity.saturating_sub(nodes.len()));
+    }
+
+    /// Enable or disable ordering the nodes by the heap size of their items
+    ///
+    /// Required for [`Self::pop_heaviest()`].
+    pub(crate) fn index_heap_sizes(&mut self, enabled: bool) {
+        if enabled == self.by_heap_size.is_some() {
+            return;
+        }
+        self.by_heap_size = enabled.then(|| {
+            self.by_capacity
+                .iter()
+                .map(|(&(capacity, Reverse(seq)), entry)| (entry.heap_size, capacity, seq))
+                .collect()
+        });
+        self.unsized_nodes.order_by_heap_size(enabled);
+    }
+
+    pub(crate) fn push(&mut self, node: Node<T>, capacity: Option<usize>, heap_size: usize) {
+        self.heap_size += heap_size;
+        let entry = Entry { heap_size, node };
+        let Some(capacity) = capacity else {
+            self.unsized_nodes.push(entry);
+            return;
+        };
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        self.by_capacity.insert((capacity, Reverse(seq)), entry);
+        if let Some(by_heap_size) = &mut self.by_heap_size {
+            by_heap_size.insert((heap_size, capacity, seq));
+        }
+    }
+
+    /// Pop a node of the smallest size class
+    pub(crate) fn pop(&mut self) -> Option<Node<T>> {
+        self.pop_with_capacity(0)
+    }
+
+    /// Pop the most recently pushed node of the largest size class
+    pub(crate) fn pop_largest(&mut self) -> Option<Node<T>> {
+        let Some((&key, _)) = self.by_capacity.last_key_value() else {
+            return self.pop_unsized();
+        };
+        self.take(key)
+    }
+
+    /// Pop the node with the largest heap size
+    ///
+    /// Prefers the most recently pushed node of the largest size class
+    /// among nodes with the same heap size. Falls back to
+    /// [`Self::pop_largest()`] unless heap sizes are indexed.
+    pub(crate) fn pop_heaviest(&mut self) -> Option<Node<T>> {
+        let Some(by_heap_size) = &self.by_heap_size else {
+            return self.pop_largest();
+        };
+        let unsized_heap_size = self.unsized_nodes.peek_heap_size();
+        match by_heap_size.last() {
+            Some(&(heap_size, capacity, seq))
+                if unsized_heap_size
+                    .is_none_or(|unsized_heap_size| heap_size >= unsized_heap_size) =>
+            {
+                self.take((capacity, Reverse(seq)))
+            }
+            _ => self.pop_unsized(),
+        }
+    }
+
+    /// Pop a node of the smallest size class with at least `min_capacity`
+    ///
+    /// Nodes without a capacity only match if `min_capacity` is 0.
+    pub(crate) fn pop_with_capacity(&mut self, min_capacity: usize) -> Option<Node<T>> {
+        if min_capacity == 0 {
+            if let Some(node) = self.pop_unsized() {
+                return Some(node);
+            }
+        }
+        let key = *self
+            .by_capacity
+            .range((min_capacity, Reverse(u64::MAX))..)
+            .next()?
+            .0;
+        self.take(key)
+    }
+
+    /// Remove all nodes
+    ///
+    /// The iterator must be exhausted.
+    pub(crate) fn drain(&mut self) -> impl Iterator<Item = Node<T>> + '_ {
+        if let Some(by_heap_size) = &mut self.by_heap_size {
+            by_heap_size.clear();
+        }
+        self.heap_size = 0;
+        let unsized_nodes = &mut self.unsized_nodes;
+        let sized_nodes = mem::take(&mut self.by_capacity).into_values();
+        iter::from_fn(|| unsized_nodes.pop())
+            .chain(sized_nodes)
+            .map(|entry| entry.node)
+    }
+
+    fn pop_unsized(&mut self) -> Option<Node<T>> {
+        let Entry { heap_size, node } = self.unsized_nodes.pop()?;
+        self.heap_size -= heap_size;
+        Some(node)
+    }
+
+    fn take(&mut self, key: CapacityKey) -> Option<Node<T>> {
+        let Entry { heap_size, node } = self.by_capacity.remove(&key)?;
+        if let Some(by_heap_size) = &mut self.by_heap_size {
+            let (capacity, Reverse(seq)) = key;
+            by_heap_size.remove(&(heap_size, capacity, seq));
+        }
+        self.heap_size -= heap_size;
+        Some(node)
+    }
+}
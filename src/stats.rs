@@ -0,0 +1,169 @@
+// SPDX-FileCopyrightText: The cirque authors
+// SPDX-License-Identifier: MPL-2.0
+
+//! Queue statistics
+
+use crate::{
+    cache_padded::CachePadded,
+    sync::{AtomicUsize, Ordering},
+};
+
+/// Counters that are only updated by the producer
+#[derive(Debug, Default)]
+struct ProducerCounters {
+    pushed: AtomicUsize,
+    max_in_flight: AtomicUsize,
+    allocated: AtomicUsize,
+    reused_recycled: AtomicUsize,
+    reused_returned: AtomicUsize,
+    recycled: AtomicUsize,
+    dropped: AtomicUsize,
+}
+
+/// Counters that are only updated by the consumer
+#[derive(Debug, Default)]
+struct ConsumerCounters {
+    popped: AtomicUsize,
+    returned: AtomicUsize,
+}
+
+/// Increment a counter that is only updated by a single thread
+///
+/// Avoids the read-modify-write of `fetch_add()`.
+#[inline]
+fn increment(counter: &AtomicUsize, count: usize) {
+    counter.store(
+        counter.load(Ordering::Relaxed).wrapping_add(count),
+        Ordering::Relaxed,
+    );
+}
+
+/// Statistics of a queue
+///
+/// Shared by the [`Producer`](crate::Producer) and the [`Consumer`](crate::Consumer).
+/// The counters of either side are placed on separate cache lines to
+/// avoid false sharing.
+///
+/// All counters are updated independently with relaxed memory ordering.
+/// Snapshots are not guaranteed to be consistent across counters.
+#[derive(Debug, Default)]
+pub struct Stats {
+    producer: CachePadded<ProducerCounters>,
+    consumer: CachePadded<ConsumerCounters>,
+}
+
+impl Stats {
+    /// Take a snapshot of all counters
+    #[must_use]
+    pub fn snapshot(&self) -> StatsSnapshot {
+        let Self { producer, consumer } = self;
+        let pushed = producer.pushed.load(Ordering::Relaxed);
+        let returned = consumer.returned.load(Ordering::Relaxed);
+        StatsSnapshot {
+            pushed,
+            popped: consumer.popped.load(Ordering::Relaxed),
+            returned,
+            in_flight: pushed.wrapping_sub(returned),
+            max_in_flight: producer.max_in_flight.load(Ordering::Relaxed),
+            allocated: producer.allocated.load(Ordering::Relaxed),
+            reused_recycled: producer.reused_recycled.load(Ordering::Relaxed),
+            reused_returned: producer.reused_returned.load(Ordering::Relaxed),
+            recycled: producer.recycled.load(Ordering::Relaxed),
+            dropped: producer.dropped.load(Ordering::Relaxed),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn record_pushed(&self) {
+        let pushed = self.producer.pushed.load(Ordering::Relaxed).wrapping_add(1);
+        self.producer.pushed.store(pushed, Ordering::Relaxed);
+        let in_flight = pushed.wrapping_sub(self.consumer.returned.load(Ordering::Relaxed));
+        if in_flight > self.producer.max_in_flight.load(Ordering::Relaxed) {
+            self.producer
+                .max_in_flight
+                .store(in_flight, Ordering::Relaxed);
+        }
+    }
+
+    #[inline]
+    pub(crate) fn record_popped(&self, count: usize) {
+        increment(&self.consumer.popped, count);
+    }
+
+    #[inline]
+    pub(crate) fn record_returned(&self, count: usize) {
+        increment(&self.consumer.returned, count);
+    }
+
+    #[inline]
+    pub(crate) fn record_allocated(&self) {
+        increment(&self.producer.allocated, 1);
+    }
+
+    #[inline]
+    pub(crate) fn record_reused_recycled(&self) {
+        increment(&self.producer.reused_recycled, 1);
+    }
+
+    #[inline]
+    pub(crate) fn record_reused_returned(&self) {
+        increment(&self.producer.reused_returned, 1);
+    }
+
+    #[inline]
+    pub(crate) fn record_recycled(&self, count: usize) {
+        increment(&self.producer.recycled, count);
+    }
+
+    #[inline]
+    pub(crate) fn record_dropped(&self) {
+        increment(&self.producer.dropped, 1);
+    }
+
+    #[inline]
+    pub(crate) fn recycled(&self) -> usize {
+        self.producer.recycled.load(Ordering::Relaxed)
+    }
+
+    #[inline]
+    pub(crate) fn dropped(&self) -> usize {
+        self.producer.dropped.load(Ordering::Relaxed)
+    }
+}
+
+/// Snapshot of [`Stats`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StatsSnapshot {
+    /// Items pushed by the producer
+    pub pushed: usize,
+
+    /// Items popped by the consumer
+    pub popped: usize,
+
+    /// Items returned by the consumer
+    pub returned: usize,
+
+    /// Items that have been pushed but not yet returned
+    pub in_flight: usize,
+
+    /// High-water mark of [`Self::in_flight`]
+    pub max_in_flight: usize,
+
+    /// Nodes that have been allocated by the producer
+    pub allocated: usize,
+
+    /// Nodes that have been reused from the recycled nodes
+    pub reused_recycled: usize,
+
+    /// Nodes that have been reused directly after being returned
+    pub reused_returned: usize,
+
+    /// Returned nodes that have been recycled
+    pub recycled: usize,
+
+    /// Nodes that have been dropped instead of being recycled or reused
+    ///
+    /// Includes recycled nodes that have been evicted later, i.e. these
+    /// nodes are counted both as recycled and as dropped.
+    pub dropped: usize,
+}
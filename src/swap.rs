@@ -0,0 +1,55 @@
+// SPDX-FileCopyrightText: The cirque authors
+// SPDX-License-Identifier: MPL-2.0
+
+//! Replacing realtime state
+//!
+//! New state objects are sent to the realtime thread that swaps them in.
+//! The displaced old state travels back to the [`Producer`] in the same node
+//! and is handed over to the [`Recycler`] for disposal outside
+//! of the realtime context.
+
+use crate::{new_producer_consumer, Consumer, Producer, Recycler, Stats};
+
+/// Create a new producer/swapper pair for replacing realtime state.
+#[must_use]
+pub fn new_producer_swapper<T, R>(
+    recycler: R,
+    recycling_capacity: usize,
+) -> (Producer<T, R>, Swapper<T>)
+where
+    R: Recycler<T>,
+{
+    let (producer, consumer) = new_producer_consumer(recycler, recycling_capacity);
+    (producer, Swapper { consumer })
+}
+
+/// Realtime side for swapping in new state
+#[allow(missing_debug_implementations)]
+pub struct Swapper<T> {
+    consumer: Consumer<T>,
+}
+
+impl<T> Swapper<T> {
+    /// Swap in the most recent state
+    ///
+    /// All pending states are swapped in successively, i.e. `current`
+    /// ends up with the most recent state. All displaced states are
+    /// returned to the producer.
+    ///
+    /// Returns `true` if `current` has been replaced.
+    pub fn swap_in(&mut self, current: &mut T) -> bool {
+        let mut swapped = false;
+        while let Some(mut item) = self.consumer.pop() {
+            core::mem::swap(&mut *item, current);
+            self.consumer.push_back(item);
+            swapped = true;
+        }
+        swapped
+    }
+
+    /// Statistics of the queue
+    #[must_use]
+    pub fn stats(&self) -> &Stats {
+        self.consumer.stats()
+    }
+}
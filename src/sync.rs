@@ -0,0 +1,29 @@
+// SPDX-FileCopyrightText: The cirque authors
+// SPDX-License-Identifier: MPL-2.0
+
+//! Synchronization primitives
+//!
+//! Replaced by their [loom](https://docs.rs/loom) counterparts when
+//! compiled with `--cfg loom` for model checking.
+//!
+//! All shared state of cirque is built from these primitives. The atomics
+//! inside of the `llq` queues are not instrumented, i.e. the interleavings
+//! of pushing, popping, and returning nodes are not modeled.
+
+#[cfg(loom)]
+pub(crate) use loom::sync::{
+    atomic::{AtomicBool, AtomicUsize, Ordering},
+    Arc,
+};
+
+#[cfg(all(loom, feature = "std"))]
+pub(crate) use loom::sync::{Mutex, MutexGuard};
+
+#[cfg(not(loom))]
+pub(crate) use alloc::sync::Arc;
+
+#[cfg(not(loom))]
+pub(crate) use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+#[cfg(all(not(loom), feature = "std"))]
+pub(crate) use std::sync::{Mutex, MutexGuard};
@@ -0,0 +1,126 @@
+// SPDX-FileCopyrightText: The cirque authors
+// SPDX-License-Identifier: MPL-2.0
+
+#![cfg(feature = "async")]
+
+use std::{
+    future::Future,
+    pin::pin,
+    sync::Arc,
+    task::{Context, Poll, Wake, Waker},
+    thread::{self, Thread},
+};
+
+use cirque::{new_producer_consumer, recyclers::NoOpRecycler};
+use futures_core::Stream;
+
+/// Unparks the blocked thread
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Run a future to completion on the current thread
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+        thread::park();
+    }
+}
+
+/// Receive the next item of a stream
+fn next<S: Stream + Unpin>(stream: &mut S) -> impl Future<Output = Option<S::Item>> + '_ {
+    std::future::poll_fn(move |cx| pin!(&mut *stream).poll_next(cx))
+}
+
+#[test]
+fn recycled_resolves_when_items_are_returned() {
+    let (mut producer, mut consumer) = new_producer_consumer(NoOpRecycler, 2);
+    producer.push(1);
+    producer.push(2);
+    let realtime_thread = thread::spawn(move || {
+        let mut popped = 0;
+        while popped < 2 {
+            if let Some(item) = consumer.pop() {
+                consumer.push_back(item);
+                popped += 1;
+            }
+        }
+        consumer
+    });
+    let mut recycled = 0;
+    while recycled < 2 {
+        recycled += block_on(producer.recycled());
+    }
+    assert_eq!(2, producer.recycled_count());
+    drop(realtime_thread.join().unwrap());
+}
+
+#[test]
+fn stream_yields_pushed_items() {
+    let (mut producer, mut consumer) = new_producer_consumer(NoOpRecycler, 0);
+    let producer_thread = thread::spawn(move || {
+        producer.push(1);
+        producer.push(2);
+        producer.push(3);
+        producer
+    });
+    let mut stream = consumer.stream();
+    let mut items = Vec::new();
+    while items.len() < 3 {
+        items.push(block_on(next(&mut stream)).unwrap().into_inner());
+    }
+    drop(producer_thread.join().unwrap());
+    assert_eq!(vec![1, 2, 3], items);
+}
+
+#[test]
+fn stream_terminates_when_producer_is_dropped() {
+    let (mut producer, mut consumer) = new_producer_consumer(NoOpRecycler, 0);
+    let producer_thread = thread::spawn(move || {
+        producer.push(1);
+        producer.push(2);
+    });
+    let mut stream = consumer.stream();
+    let mut items = Vec::new();
+    while let Some(item) = block_on(next(&mut stream)) {
+        items.push(item.into_inner());
+    }
+    producer_thread.join().unwrap();
+    assert_eq!(vec![1, 2], items);
+    assert!(block_on(next(&mut stream)).is_none());
+}
+
+#[test]
+fn recycled_releases_waker_when_dropped() {
+    let (mut producer, _consumer) = new_producer_consumer::<i32, _>(NoOpRecycler, 1);
+    let thread_waker = Arc::new(ThreadWaker(thread::current()));
+    let waker = Waker::from(Arc::clone(&thread_waker));
+    let mut cx = Context::from_waker(&waker);
+    let mut recycled = Box::pin(producer.recycled());
+    assert!(recycled.as_mut().poll(&mut cx).is_pending());
+    assert_eq!(3, Arc::strong_count(&thread_waker));
+    drop(recycled);
+    assert_eq!(2, Arc::strong_count(&thread_waker));
+}
+
+#[test]
+fn stream_releases_waker_when_dropped() {
+    let (_producer, mut consumer) = new_producer_consumer::<i32, _>(NoOpRecycler, 0);
+    let thread_waker = Arc::new(ThreadWaker(thread::current()));
+    let waker = Waker::from(Arc::clone(&thread_waker));
+    let mut cx = Context::from_waker(&waker);
+    let mut stream = consumer.stream();
+    assert!(pin!(&mut stream).poll_next(&mut cx).is_pending());
+    assert_eq!(3, Arc::strong_count(&thread_waker));
+    drop(stream);
+    assert_eq!(2, Arc::strong_count(&thread_waker));
+}
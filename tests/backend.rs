@@ -0,0 +1,61 @@
+// SPDX-FileCopyrightText: The cirque authors
+// SPDX-License-Identifier: MPL-2.0
+
+use std::{cell::Cell, rc::Rc};
+
+use cirque::{
+    backend::{LinkedList, LinkedListRx, LinkedListTx, NodeTx, RingBackend},
+    new_producer_consumer_with_backend,
+    recyclers::NoOpRecycler,
+    Node,
+};
+
+/// Counts all nodes that are pushed into any queue of the ring
+#[derive(Clone, Default)]
+struct CountingBackend {
+    pushed: Rc<Cell<usize>>,
+}
+
+struct CountingTx<T> {
+    tx: LinkedListTx<T>,
+    pushed: Rc<Cell<usize>>,
+}
+
+impl<T> NodeTx<T> for CountingTx<T> {
+    fn push(&mut self, node: Node<T>) {
+        self.pushed.set(self.pushed.get() + 1);
+        self.tx.push(node);
+    }
+}
+
+impl<T> RingBackend<T> for CountingBackend {
+    type Tx = CountingTx<T>;
+    type Rx = LinkedListRx<T>;
+
+    fn new_queue(&mut self) -> (Self::Tx, Self::Rx) {
+        let (tx, rx) = LinkedList.new_queue();
+        let tx = CountingTx {
+            tx,
+            pushed: Rc::clone(&self.pushed),
+        };
+        (tx, rx)
+    }
+}
+
+#[test]
+fn transport_nodes_through_custom_backend() {
+    let backend = CountingBackend::default();
+    let (mut producer, mut consumer) =
+        new_producer_consumer_with_backend(NoOpRecycler, 2, backend.clone());
+    producer.push_iter([1, 2]);
+    assert_eq!(2, backend.pushed.get());
+
+    let item = consumer.pop().unwrap();
+    assert_eq!(1, *item);
+    consumer.push_back(item);
+    consumer.drain();
+    assert_eq!(4, backend.pushed.get());
+
+    producer.drain_and_recycle();
+    assert_eq!(2, producer.recycled_count());
+}
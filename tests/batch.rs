@@ -0,0 +1,34 @@
+// SPDX-FileCopyrightText: The cirque authors
+// SPDX-License-Identifier: MPL-2.0
+
+use cirque::{new_producer_consumer, recyclers::NoOpRecycler};
+
+#[test]
+fn pop_batch_and_push_back_all() {
+    let (mut producer, mut consumer) = new_producer_consumer(NoOpRecycler, 5);
+    producer.push_iter(1..=5);
+
+    let mut items = Vec::with_capacity(3);
+    assert_eq!(3, consumer.pop_batch(&mut items, 3));
+    assert!(items.iter().map(|item| **item).eq(1..=3));
+
+    // Appended to the existing items
+    assert_eq!(2, consumer.pop_batch(&mut items, 3));
+    assert_eq!(0, consumer.pop_batch(&mut items, 3));
+    assert!(items.iter().map(|item| **item).eq(1..=5));
+
+    consumer.push_back_all(items.drain(..));
+    let stats = consumer.stats().snapshot();
+    assert_eq!(5, stats.popped);
+    assert_eq!(5, stats.returned);
+
+    producer.drain_and_recycle();
+    assert_eq!(5, producer.recycled_count());
+
+    // All recycled nodes are reused
+    producer.push_iter(6..=10);
+    assert_eq!(5, producer.stats().snapshot().allocated);
+    assert_eq!(5, consumer.pop_batch(&mut items, usize::MAX));
+    assert!(items.iter().map(|item| **item).eq(6..=10));
+    consumer.push_back_all(items);
+}
@@ -0,0 +1,72 @@
+// SPDX-FileCopyrightText: The cirque authors
+// SPDX-License-Identifier: MPL-2.0
+
+use cirque::{new_producer_consumer_bounded, recyclers::ClearRecycler, TryPushError};
+
+#[test]
+fn try_push_exhausted() {
+    let (mut producer, mut consumer) =
+        new_producer_consumer_bounded(ClearRecycler, 2, Vec::<u8>::new);
+    assert_eq!(2, producer.stats().snapshot().allocated);
+
+    assert_eq!(Ok(()), producer.try_push(vec![1]));
+    assert_eq!(Ok(()), producer.try_push(vec![2]));
+    assert_eq!(
+        Err(TryPushError::Exhausted(vec![3])),
+        producer.try_push(vec![3])
+    );
+
+    // Returned nodes are reused without allocating new ones
+    let item = consumer.pop().unwrap();
+    assert_eq!(vec![1], *item);
+    consumer.push_back(item);
+    assert_eq!(Ok(()), producer.try_push(vec![3]));
+    assert_eq!(
+        Err(TryPushError::Exhausted(vec![4])),
+        producer.try_push(vec![4])
+    );
+    assert_eq!(2, producer.stats().snapshot().allocated);
+
+    // Pushing still allocates new nodes
+    producer.push(vec![4]);
+    assert_eq!(3, producer.stats().snapshot().allocated);
+
+    let mut items = Vec::new();
+    while let Some(item) = consumer.pop() {
+        items.push(item[0]);
+        consumer.push_back(item);
+    }
+    assert_eq!(vec![2, 3, 4], items);
+}
+
+#[test]
+fn pool_nodes_are_never_dropped() {
+    let (mut producer, _consumer) = new_producer_consumer_bounded(ClearRecycler, 2, Vec::<u8>::new);
+
+    producer.tune_recycling_capacity(0);
+    assert_eq!(Ok(()), producer.try_push(vec![1]));
+    assert_eq!(Ok(()), producer.try_push(vec![2]));
+    assert_eq!(
+        Err(TryPushError::Exhausted(vec![3])),
+        producer.try_push(vec![3])
+    );
+}
+
+#[test]
+fn try_push_with_exhausted() {
+    let (mut producer, mut consumer) =
+        new_producer_consumer_bounded(ClearRecycler, 1, Vec::<u8>::new);
+
+    assert!(producer.try_push_with(|item| item.push(1)).is_ok());
+    let Err(TryPushError::Exhausted(fill)) = producer.try_push_with(|item| item.push(2)) else {
+        panic!("pool not exhausted");
+    };
+
+    // The rejected closure is handed back
+    let item = consumer.pop().unwrap();
+    assert_eq!(vec![1], *item);
+    consumer.push_back(item);
+    assert!(producer.try_push_with(fill).is_ok());
+    assert_eq!(1, producer.stats().snapshot().allocated);
+    assert_eq!(vec![2], consumer.pop().unwrap().into_inner());
+}
@@ -0,0 +1,56 @@
+// SPDX-FileCopyrightText: The cirque authors
+// SPDX-License-Identifier: MPL-2.0
+
+use std::{cell::Cell, rc::Rc};
+
+use cirque::{broadcast::new_producer_consumers, Recycler};
+
+/// Counts all recycled and disposed items
+#[derive(Clone, Default)]
+struct CountingRecycler {
+    recycled: Rc<Cell<usize>>,
+    disposed: Rc<Cell<usize>>,
+}
+
+impl Recycler<Vec<u8>> for CountingRecycler {
+    fn recycle(&mut self, item: &mut Vec<u8>) {
+        item.clear();
+        self.recycled.set(self.recycled.get() + 1);
+    }
+
+    fn dispose(&mut self, _item: Vec<u8>) {
+        self.disposed.set(self.disposed.get() + 1);
+    }
+}
+
+#[test]
+fn push_to_all_consumers() {
+    let recycler = CountingRecycler::default();
+    let (mut producer, mut consumers) = new_producer_consumers(recycler.clone(), 0, 3);
+    assert_eq!(3, producer.consumer_count());
+
+    producer.push(vec![1]);
+    for consumer in &mut consumers {
+        let item = consumer.pop().unwrap();
+        assert_eq!(vec![1], *item);
+        consumer.push_back(item);
+        assert!(consumer.pop().is_none());
+    }
+
+    // All returned items are recycled when reused in-place and
+    // disposed when replaced.
+    producer.push(vec![2]);
+    for consumer in &mut consumers {
+        let item = consumer.pop().unwrap();
+        assert_eq!(vec![2], *item);
+        consumer.push_back(item);
+    }
+    assert_eq!(2, recycler.recycled.get());
+    assert_eq!(1, recycler.disposed.get());
+}
+
+#[test]
+#[should_panic(expected = "consumer count must not be 0")]
+fn no_consumers() {
+    let _ = new_producer_consumers::<Vec<u8>, _>(CountingRecycler::default(), 0, 0);
+}
@@ -0,0 +1,43 @@
+// SPDX-FileCopyrightText: The cirque authors
+// SPDX-License-Identifier: MPL-2.0
+
+use cirque::{new_producer_consumer, recyclers::NoOpRecycler, TryPushError};
+
+#[test]
+fn consumer_dropped() {
+    let (mut producer, mut consumer) = new_producer_consumer(NoOpRecycler, 2);
+    assert!(producer.is_consumer_alive());
+    assert!(consumer.is_producer_alive());
+
+    producer.push(1);
+    producer.push(2);
+    let item = consumer.pop().unwrap();
+    consumer.push_back(item);
+    drop(consumer);
+    assert!(!producer.is_consumer_alive());
+
+    assert_eq!(Err(TryPushError::Disconnected(3)), producer.try_push(3));
+    assert!(matches!(
+        producer.try_push_with(|item| *item = 3),
+        Err(TryPushError::Disconnected(_))
+    ));
+
+    // All pending items have been returned by the consumer
+    producer.drain_and_recycle();
+    assert_eq!(2, producer.recycled_count());
+    assert_eq!(0, producer.in_flight());
+}
+
+#[test]
+fn producer_dropped() {
+    let (mut producer, mut consumer) = new_producer_consumer(NoOpRecycler, 0);
+    producer.push(1);
+    drop(producer);
+    assert!(!consumer.is_producer_alive());
+
+    // Pending items could still be consumed
+    let item = consumer.pop().unwrap();
+    assert_eq!(1, *item);
+    consumer.push_back(item);
+    assert!(consumer.pop().is_none());
+}
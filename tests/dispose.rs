@@ -0,0 +1,67 @@
+// SPDX-FileCopyrightText: The cirque authors
+// SPDX-License-Identifier: MPL-2.0
+
+use std::{cell::RefCell, rc::Rc};
+
+use cirque::{new_producer_consumer, Recycler};
+
+/// Records all disposed items
+struct Disposed(Rc<RefCell<Vec<u8>>>);
+
+impl Recycler<u8> for Disposed {
+    fn recycle(&mut self, _item: &mut u8) {}
+
+    fn dispose(&mut self, item: u8) {
+        self.0.borrow_mut().push(item);
+    }
+}
+
+#[test]
+fn dispose_items_exceeding_recycling_capacity() {
+    let disposed = Rc::default();
+    let (mut producer, mut consumer) = new_producer_consumer(Disposed(Rc::clone(&disposed)), 1);
+    producer.push_iter([1, 2, 3]);
+    consumer.drain();
+
+    producer.drain_and_recycle();
+    assert_eq!(1, producer.recycled_count());
+    assert_eq!(2, producer.dropped_count());
+    assert_eq!(vec![2, 3], *disposed.borrow());
+}
+
+#[test]
+fn dispose_replaced_items_of_reused_nodes() {
+    let disposed = Rc::default();
+    let (mut producer, mut consumer) = new_producer_consumer(Disposed(Rc::clone(&disposed)), 1);
+    producer.push(1);
+    consumer.drain();
+    producer.drain_and_recycle();
+
+    // Reuses the recycled node
+    producer.push(2);
+    consumer.drain();
+    // Reuses the returned node
+    producer.push(3);
+    consumer.drain();
+
+    assert_eq!(vec![1, 2], *disposed.borrow());
+    assert_eq!(1, producer.recycled_count());
+    assert_eq!(0, producer.dropped_count());
+}
+
+#[test]
+fn dispose_recycled_and_returned_items_when_dropping_producer() {
+    let disposed = Rc::default();
+    let (mut producer, mut consumer) = new_producer_consumer(Disposed(Rc::clone(&disposed)), 2);
+    producer.push_iter([1, 2, 3]);
+    let item = consumer.pop().unwrap();
+    consumer.push_back(item);
+    producer.drain_and_recycle();
+    let item = consumer.pop().unwrap();
+    consumer.push_back(item);
+    assert!(disposed.borrow().is_empty());
+
+    // The pending item is dropped together with the consumer.
+    drop(producer);
+    assert_eq!(vec![1, 2], *disposed.borrow());
+}
@@ -0,0 +1,26 @@
+// SPDX-FileCopyrightText: The cirque authors
+// SPDX-License-Identifier: MPL-2.0
+
+#![cfg(all(feature = "drop-check", debug_assertions))]
+
+use cirque::{new_producer_consumer, recyclers::NoOpRecycler};
+
+#[test]
+fn handing_back_items_does_not_panic() {
+    let (mut producer, mut consumer) = new_producer_consumer(NoOpRecycler, 1);
+    producer.push(1);
+    producer.push(2);
+
+    let item = consumer.pop().unwrap();
+    consumer.push_back(item);
+    assert_eq!(2, consumer.pop().unwrap().into_inner());
+}
+
+#[test]
+#[should_panic(expected = "consumable item dropped")]
+fn dropping_items_panics() {
+    let (mut producer, mut consumer) = new_producer_consumer(NoOpRecycler, 1);
+    producer.push(1);
+
+    drop(consumer.pop());
+}
@@ -0,0 +1,31 @@
+// SPDX-FileCopyrightText: The cirque authors
+// SPDX-License-Identifier: MPL-2.0
+
+use cirque::{duplex::new_producer_consumer, recyclers::NoOpRecycler};
+
+#[test]
+fn pop_replies_in_push_back_order() {
+    let (mut producer, mut consumer) = new_producer_consumer::<u32, String, _>(NoOpRecycler, 3);
+    for request in 1..=3 {
+        producer.push(request);
+    }
+    assert_eq!(None, producer.pop_reply());
+
+    let mut exchanges = Vec::new();
+    while let Some(mut exchange) = consumer.pop() {
+        let reply = exchange.request().to_string();
+        exchange.reply(reply);
+        assert!(exchange.has_reply());
+        exchanges.push(exchange);
+    }
+    assert_eq!(3, exchanges.len());
+
+    // Replace the reply of the second exchange
+    exchanges[1].reply("replaced".to_owned());
+
+    consumer.push_back_all(exchanges.into_iter().rev());
+    assert_eq!(Some("3".to_owned()), producer.pop_reply());
+    assert_eq!(Some("replaced".to_owned()), producer.pop_reply());
+    assert_eq!(Some("1".to_owned()), producer.pop_reply());
+    assert_eq!(None, producer.pop_reply());
+}
@@ -0,0 +1,38 @@
+// SPDX-FileCopyrightText: The cirque authors
+// SPDX-License-Identifier: MPL-2.0
+
+use cirque::{new_producer_consumer, recyclers::NoOpRecycler, TryPushError};
+
+#[test]
+fn try_push_full() {
+    let (mut producer, mut consumer) = new_producer_consumer(NoOpRecycler, 2);
+    assert_eq!(None, producer.in_flight_limit());
+    producer.set_in_flight_limit(Some(2));
+
+    assert_eq!(Ok(()), producer.try_push(1));
+    assert!(producer.try_push_with(|item| *item = 2).is_ok());
+    assert_eq!(2, producer.in_flight());
+    assert_eq!(Err(TryPushError::Full(3)), producer.try_push(3));
+    assert!(matches!(
+        producer.try_push_with(|item| *item = 3),
+        Err(TryPushError::Full(_))
+    ));
+
+    // Pushing ignores the limit
+    producer.push(3);
+    assert_eq!(3, producer.in_flight());
+
+    // Returned items are received when the limit is reached
+    let item = consumer.pop().unwrap();
+    consumer.push_back(item);
+    assert_eq!(3, producer.in_flight());
+    assert_eq!(Err(TryPushError::Full(4)), producer.try_push(4));
+    assert_eq!(2, producer.in_flight());
+    consumer.drain();
+    assert_eq!(Ok(()), producer.try_push(4));
+    assert_eq!(1, producer.in_flight());
+
+    producer.set_in_flight_limit(None);
+    assert_eq!(Ok(()), producer.try_push(5));
+    assert_eq!(2, producer.in_flight());
+}
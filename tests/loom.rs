@@ -0,0 +1,129 @@
+// SPDX-FileCopyrightText: The cirque authors
+// SPDX-License-Identifier: MPL-2.0
+
+//! Model checking of the ring protocol
+//!
+//! Covers the statistics and the shutdown state. The atomics inside of
+//! the `llq` queues are not instrumented, i.e. their interleavings are
+//! not explored.
+//!
+//! Run with `RUSTFLAGS="--cfg loom" cargo test --release --test loom`.
+
+#![cfg(loom)]
+
+use cirque::{new_producer_consumer, recyclers::ClearRecycler};
+use loom::thread;
+
+#[test]
+fn push_pop_push_back() {
+    loom::model(|| {
+        let (mut producer, mut consumer) = new_producer_consumer(ClearRecycler, 2);
+        let producer_thread = thread::spawn(move || {
+            producer.push(vec![1]);
+            producer.push(vec![2]);
+            producer
+        });
+        let mut popped = Vec::new();
+        for _ in 0..2 {
+            if let Some(item) = consumer.pop() {
+                popped.push(item[0]);
+                consumer.push_back(item);
+            }
+        }
+        let mut producer = producer_thread.join().unwrap();
+        while let Some(item) = consumer.pop() {
+            popped.push(item[0]);
+            consumer.push_back(item);
+        }
+        assert_eq!(vec![1, 2], popped);
+
+        producer.drain_and_recycle();
+        let stats = producer.stats().snapshot();
+        // Returned nodes might have been reused while pushing.
+        assert_eq!(stats.allocated, producer.recycled_count());
+        assert_eq!(2, stats.pushed);
+        assert_eq!(2, stats.popped);
+        assert_eq!(2, stats.returned);
+        assert_eq!(0, stats.in_flight);
+    });
+}
+
+#[test]
+fn drain_and_recycle_while_pushing_back() {
+    loom::model(|| {
+        let (mut producer, mut consumer) = new_producer_consumer(ClearRecycler, 1);
+        producer.push(vec![1]);
+        producer.push(vec![2]);
+        let consumer_thread = thread::spawn(move || {
+            while let Some(item) = consumer.pop() {
+                consumer.push_back(item);
+            }
+            consumer
+        });
+        producer.drain_and_recycle();
+        // Reuses either a recycled or a returned node or allocates a new one
+        producer.push(vec![3]);
+        let mut consumer = consumer_thread.join().unwrap();
+        consumer.drain();
+        producer.drain_and_recycle();
+
+        let stats = producer.stats().snapshot();
+        assert_eq!(3, stats.pushed);
+        assert_eq!(3, stats.returned);
+        assert_eq!(
+            stats.pushed,
+            stats.allocated + stats.reused_recycled + stats.reused_returned
+        );
+        // All nodes that have not been reused are either recycled or dropped.
+        assert_eq!(
+            stats.allocated,
+            producer.recycled_count() - stats.reused_recycled + stats.dropped
+        );
+    });
+}
+
+#[test]
+fn drain_concurrently() {
+    loom::model(|| {
+        let (mut producer, mut consumer) = new_producer_consumer(ClearRecycler, 2);
+        let producer_thread = thread::spawn(move || {
+            producer.push_iter([vec![1], vec![2]]);
+            producer.drain_and_recycle();
+            producer
+        });
+        consumer.drain();
+        let mut producer = producer_thread.join().unwrap();
+        consumer.drain();
+        producer.drain_and_recycle();
+
+        assert!(consumer.pop().is_none());
+        let stats = consumer.stats().snapshot();
+        assert_eq!(2, stats.returned);
+        assert_eq!(stats.allocated, producer.recycled_count());
+    });
+}
+
+#[test]
+fn receive_all_items_before_producer_is_dropped() {
+    loom::model(|| {
+        let (mut producer, mut consumer) = new_producer_consumer(ClearRecycler, 1);
+        let producer_thread = thread::spawn(move || {
+            producer.push(vec![1]);
+        });
+        let mut popped = Vec::new();
+        loop {
+            // Items that have been pushed before dropping the producer
+            // must be visible after observing the drop.
+            let is_producer_alive = consumer.is_producer_alive();
+            if let Some(item) = consumer.pop() {
+                popped.push(item[0]);
+                consumer.push_back(item);
+            } else if !is_producer_alive {
+                break;
+            }
+            thread::yield_now();
+        }
+        producer_thread.join().unwrap();
+        assert_eq!(vec![1], popped);
+    });
+}
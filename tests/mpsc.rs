@@ -0,0 +1,42 @@
+// SPDX-FileCopyrightText: The cirque authors
+// SPDX-License-Identifier: MPL-2.0
+
+#![cfg(feature = "std")]
+
+use std::thread;
+
+use cirque::{mpsc::new_producer_consumer, recyclers::NoOpRecycler};
+
+#[test]
+fn push_from_multiple_threads() {
+    let (producer, mut consumer) = new_producer_consumer(NoOpRecycler, 0);
+    let producers = (0..4u32)
+        .map(|index| {
+            let producer = producer.clone();
+            thread::spawn(move || {
+                // Batches are never interleaved with items of other producers.
+                producer.push_iter((0..10).map(|item| index * 100 + item));
+            })
+        })
+        .collect::<Vec<_>>();
+    for producer in producers {
+        producer.join().unwrap();
+    }
+
+    let mut items = Vec::new();
+    while let Some(item) = consumer.pop() {
+        items.push(*item);
+        consumer.push_back(item);
+    }
+    assert_eq!(40, items.len());
+    for batch in items.chunks(10) {
+        let first = batch[0];
+        assert_eq!(0, first % 100);
+        assert!(batch.iter().copied().eq(first..first + 10));
+    }
+
+    let stats = producer.stats();
+    assert_eq!(40, stats.pushed);
+    assert_eq!(40, stats.popped);
+    assert_eq!(40, stats.returned);
+}
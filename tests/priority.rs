@@ -0,0 +1,59 @@
+// SPDX-FileCopyrightText: The cirque authors
+// SPDX-License-Identifier: MPL-2.0
+
+use cirque::{priority::new_producer_consumer, recyclers::ClearRecycler};
+
+#[test]
+fn pop_higher_lanes_first() {
+    let (mut producer, mut consumer) = new_producer_consumer(ClearRecycler, 8, 3);
+    assert_eq!(3, producer.lane_count());
+    assert_eq!(3, consumer.lane_count());
+
+    producer.push(2, vec![1]);
+    producer.push(1, vec![2]);
+    producer.push(2, vec![3]);
+    producer.push(0, vec![4]);
+
+    let mut items = Vec::new();
+    while let Some(item) = consumer.pop() {
+        items.push(item[0]);
+        consumer.push_back(item);
+    }
+    assert_eq!(vec![4, 2, 1, 3], items);
+
+    // All lanes share the same return path
+    producer.drain_and_recycle();
+    let snapshot = producer.stats().snapshot();
+    assert_eq!(4, snapshot.pushed);
+    assert_eq!(4, snapshot.popped);
+    assert_eq!(4, snapshot.returned);
+    assert_eq!(4, snapshot.recycled);
+}
+
+#[test]
+fn drain_all_lanes() {
+    let (mut producer, mut consumer) = new_producer_consumer(ClearRecycler, 8, 2);
+    producer.push(0, vec![1]);
+    producer.push(1, vec![2]);
+
+    consumer.drain();
+    assert!(consumer.pop().is_none());
+    assert_eq!(2, consumer.stats().snapshot().returned);
+}
+
+#[test]
+fn return_all_lanes_when_dropped() {
+    let (mut producer, consumer) = new_producer_consumer(ClearRecycler, 8, 2);
+    producer.push(0, vec![1]);
+    producer.push(1, vec![2]);
+
+    drop(consumer);
+    producer.drain_and_recycle();
+    assert_eq!(2, producer.stats().snapshot().recycled);
+}
+
+#[test]
+#[should_panic(expected = "at least one lane is required")]
+fn no_lanes() {
+    let _ = new_producer_consumer::<Vec<u8>, _>(ClearRecycler, 0, 0);
+}
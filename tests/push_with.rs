@@ -0,0 +1,38 @@
+// SPDX-FileCopyrightText: The cirque authors
+// SPDX-License-Identifier: MPL-2.0
+
+use cirque::{new_producer_consumer, recyclers::ClearRecycler};
+
+#[test]
+fn fill_items_in_place() {
+    let (mut producer, mut consumer) = new_producer_consumer(ClearRecycler, 1);
+
+    // A new item is created by default
+    producer.push_with(|buf: &mut Vec<u8>| {
+        assert!(buf.is_empty());
+        buf.extend_from_slice(&[1; 64]);
+    });
+    let item = consumer.pop().unwrap();
+    assert_eq!(vec![1; 64], *item);
+    consumer.push_back(item);
+
+    // Returned items are recycled before being filled
+    producer.push_with(|buf| {
+        assert!(buf.is_empty());
+        assert!(buf.capacity() >= 64);
+        buf.push(2);
+    });
+    let item = consumer.pop().unwrap();
+    assert_eq!(vec![2], *item);
+    consumer.push_back(item);
+
+    // Recycled items are reused as well
+    producer.drain_and_recycle();
+    assert_eq!(1, producer.recycled_count());
+    producer.push_with(|buf| {
+        assert!(buf.is_empty());
+        assert!(buf.capacity() >= 64);
+    });
+    assert_eq!(1, producer.stats().snapshot().allocated);
+    assert_eq!(Vec::<u8>::new(), consumer.pop().unwrap().into_inner());
+}
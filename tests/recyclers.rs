@@ -0,0 +1,77 @@
+// SPDX-FileCopyrightText: The cirque authors
+// SPDX-License-Identifier: MPL-2.0
+
+use std::collections::VecDeque;
+
+use cirque::{
+    new_producer_consumer,
+    recyclers::{ClearRecycler, NoOpRecycler, ShrinkRecycler},
+    Recycler,
+};
+
+/// Push an item, return it, and fill it in-place after recycling
+fn recycle_once<T, R>(recycler: R, item: T) -> T
+where
+    T: Default,
+    R: Recycler<T>,
+{
+    let (mut producer, mut consumer) = new_producer_consumer(recycler, 1);
+    producer.push(item);
+    consumer.drain();
+    producer.drain_and_recycle();
+    assert_eq!(1, producer.recycled_count());
+
+    let mut recycled = None;
+    producer.push_with(|item: &mut T| recycled = Some(std::mem::take(item)));
+    consumer.drain();
+    recycled.unwrap()
+}
+
+#[test]
+fn no_op() {
+    assert_eq!(vec![1, 2], recycle_once(NoOpRecycler, vec![1, 2]));
+}
+
+#[test]
+fn clear_retains_capacity() {
+    let item = recycle_once(ClearRecycler, Vec::<u8>::with_capacity(64));
+    assert!(item.is_empty());
+    assert!(item.capacity() >= 64);
+
+    let item = recycle_once(ClearRecycler, VecDeque::from(vec![1u8; 64]));
+    assert!(item.is_empty());
+    assert!(item.capacity() >= 64);
+
+    let item = recycle_once(ClearRecycler, "cirque".to_owned());
+    assert!(item.is_empty());
+    assert!(item.capacity() >= 6);
+
+    assert_eq!(None, recycle_once(ClearRecycler, Some(1)));
+}
+
+#[test]
+fn shrink_oversized_buffers() {
+    let recycler = ShrinkRecycler::new(16);
+
+    let item = recycle_once(recycler, vec![1u8; 8]);
+    assert!(item.is_empty());
+    assert!(item.capacity() >= 8);
+
+    let item = recycle_once(recycler, vec![1u8; 64]);
+    assert!(item.is_empty());
+    assert!((16..64).contains(&item.capacity()));
+
+    let item = recycle_once(recycler, VecDeque::from(vec![1u8; 64]));
+    assert!(item.is_empty());
+    assert!((16..64).contains(&item.capacity()));
+
+    let item = recycle_once(recycler, "x".repeat(64));
+    assert!(item.is_empty());
+    assert!((16..64).contains(&item.capacity()));
+}
+
+#[test]
+fn closure() {
+    let item = recycle_once(|item: &mut Vec<u8>| item.truncate(1), vec![1, 2]);
+    assert_eq!(vec![1], item);
+}
@@ -0,0 +1,99 @@
+// SPDX-FileCopyrightText: The cirque authors
+// SPDX-License-Identifier: MPL-2.0
+
+use cirque::{
+    new_producer_consumer, new_producer_consumer_bounded,
+    recyclers::{ClearRecycler, HeapSizeRecycler},
+};
+
+#[test]
+fn unlimited_by_default() {
+    let (mut producer, mut consumer) = new_producer_consumer(ClearRecycler, 3);
+    producer.push_iter([vec![0_u8; 16], vec![0; 1024], vec![0; 64]]);
+    consumer.drain();
+    assert_eq!(None, producer.recycling_budget());
+
+    producer.drain_and_recycle();
+    assert_eq!(1104, producer.recycled_heap_size());
+    assert_eq!(0, producer.dropped_count());
+}
+
+#[test]
+fn unlimited_after_disabling_budget() {
+    let (mut producer, mut consumer) = new_producer_consumer(ClearRecycler, 3);
+    producer.push_iter([vec![0_u8; 16], vec![0; 1024], vec![0; 64]]);
+    consumer.drain();
+    producer.set_recycling_budget(Some(100));
+    producer.set_recycling_budget(None);
+
+    producer.drain_and_recycle();
+    assert_eq!(1104, producer.recycled_heap_size());
+    assert_eq!(0, producer.dropped_count());
+}
+
+#[test]
+fn drop_largest_items_when_receiving() {
+    let (mut producer, mut consumer) = new_producer_consumer(ClearRecycler, 3);
+    producer.push_iter([vec![0_u8; 16], vec![0; 1024], vec![0; 64]]);
+    consumer.drain();
+    producer.set_recycling_budget(Some(100));
+
+    producer.drain_and_recycle();
+    assert_eq!(3, producer.recycled_count());
+    assert_eq!(1, producer.dropped_count());
+    assert_eq!(80, producer.recycled_heap_size());
+}
+
+#[test]
+fn drop_largest_items_when_lowering_budget() {
+    let (mut producer, mut consumer) = new_producer_consumer(ClearRecycler, 3);
+    producer.push_iter([vec![0_u8; 16], vec![0; 1024], vec![0; 64]]);
+    consumer.drain();
+    producer.drain_and_recycle();
+
+    producer.set_recycling_budget(Some(20));
+    assert_eq!(2, producer.dropped_count());
+    assert_eq!(16, producer.recycled_heap_size());
+
+    producer.set_recycling_budget(Some(0));
+    assert_eq!(3, producer.dropped_count());
+    assert_eq!(0, producer.recycled_heap_size());
+}
+
+#[test]
+fn pool_nodes_are_never_dropped() {
+    let (mut producer, _consumer) =
+        new_producer_consumer_bounded(ClearRecycler, 2, || Vec::<u8>::with_capacity(64));
+    let heap_size = producer.recycled_heap_size();
+    assert!(heap_size >= 128);
+
+    producer.set_recycling_budget(Some(0));
+    assert_eq!(0, producer.dropped_count());
+    assert_eq!(heap_size, producer.recycled_heap_size());
+}
+
+#[test]
+fn measure_heap_size_with_closure() {
+    // Measure the length instead of the capacity
+    let recycler = HeapSizeRecycler::new(|_: &mut Vec<u8>| {}, |item: &Vec<u8>| item.len());
+    let (mut producer, mut consumer) = new_producer_consumer(recycler, 2);
+    producer.set_recycling_budget(Some(10));
+    producer.push_iter([vec![0; 8], vec![0; 4]]);
+    consumer.drain();
+
+    producer.drain_and_recycle();
+    assert_eq!(1, producer.dropped_count());
+    assert_eq!(4, producer.recycled_heap_size());
+}
+
+#[test]
+fn keep_smallest_items_of_many() {
+    let (mut producer, mut consumer) = new_producer_consumer(ClearRecycler, 1000);
+    producer.push_iter((1..=1000).map(|len| vec![0_u8; len]));
+    consumer.drain();
+    producer.set_recycling_budget(Some((1..=100).sum()));
+
+    producer.drain_and_recycle();
+    assert_eq!(900, producer.dropped_count());
+    assert_eq!(5050, producer.recycled_heap_size());
+}
@@ -0,0 +1,98 @@
+// SPDX-FileCopyrightText: The cirque authors
+// SPDX-License-Identifier: MPL-2.0
+
+#![cfg(feature = "rt-check")]
+
+use std::alloc::System;
+
+use cirque::{
+    new_producer_consumer, new_producer_consumer_bounded,
+    recyclers::ClearRecycler,
+    rt_check::{assert_no_alloc, count_alloc, CountingAllocator},
+    ConsumableItem,
+};
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator<System> = CountingAllocator::new(System);
+
+#[test]
+fn detect_allocations() {
+    let ((), counts) = count_alloc(|| drop(vec![1u8]));
+    assert_eq!(1, counts.allocations);
+    assert_eq!(1, counts.deallocations);
+}
+
+#[test]
+fn full_ring_without_allocations_on_consumer() {
+    let (mut producer, mut consumer) = new_producer_consumer(ClearRecycler, 3);
+    for round in 0..3 {
+        producer.push(vec![round]);
+        producer.push_iter([vec![round], vec![round]]);
+
+        let mut items = Vec::with_capacity(2);
+        assert_no_alloc(|| {
+            let item = consumer.pop().unwrap();
+            assert_eq!(round, item[0]);
+            consumer.push_back(item);
+            assert_eq!(2, consumer.pop_batch(&mut items, 2));
+            consumer.push_back_all(items.drain(..));
+            assert!(consumer.pop().is_none());
+        });
+
+        producer.drain_and_recycle();
+    }
+    assert_eq!(3, producer.stats().snapshot().allocated);
+}
+
+#[test]
+fn drain_without_allocations_on_consumer() {
+    let (mut producer, mut consumer) = new_producer_consumer(ClearRecycler, 2);
+    producer.push(vec![1]);
+    producer.push(vec![2]);
+
+    assert_no_alloc(|| consumer.drain());
+
+    producer.drain_and_recycle();
+    assert_eq!(2, producer.recycled_count());
+}
+
+#[test]
+fn recycle_buffers_without_allocations() {
+    const POOL_SIZE: usize = 32;
+    let (mut producer, mut consumer) =
+        new_producer_consumer_bounded(ClearRecycler, POOL_SIZE, || Vec::<u8>::with_capacity(16));
+    for recycling_budget in [None, Some(POOL_SIZE * 16)] {
+        producer.set_recycling_budget(recycling_budget);
+        let ((), counts) = count_alloc(|| {
+            for round in 0..3 {
+                for _ in 0..POOL_SIZE {
+                    assert!(producer.try_push_with(|item| item.push(round)).is_ok());
+                }
+                consumer.drain();
+                producer.drain_and_recycle();
+            }
+        });
+        assert!(counts.is_zero());
+    }
+    assert_eq!(POOL_SIZE, producer.stats().snapshot().allocated);
+}
+
+#[test]
+fn discarding_consumable_item_deallocates() {
+    let (mut producer, mut consumer) = new_producer_consumer(ClearRecycler, 1);
+    producer.push(vec![1]);
+
+    let ((), counts) = count_alloc(|| drop(consumer.pop().unwrap().into_inner()));
+    // Both the node and the buffer of the item are freed.
+    assert_eq!(0, counts.allocations);
+    assert_eq!(2, counts.deallocations);
+}
+
+#[test]
+#[should_panic(expected = "unexpected (de-)allocations in realtime context")]
+fn assert_no_alloc_panics() {
+    let (mut producer, mut consumer) = new_producer_consumer(ClearRecycler, 1);
+    producer.push(vec![1]);
+
+    assert_no_alloc(|| consumer.pop().map(ConsumableItem::into_inner));
+}
@@ -0,0 +1,106 @@
+// SPDX-FileCopyrightText: The cirque authors
+// SPDX-License-Identifier: MPL-2.0
+
+use cirque::{recyclers::ClearRecycler, schedule::new_producer_consumer};
+
+#[test]
+fn pop_due_in_order_of_time() {
+    let (mut producer, mut consumer) = new_producer_consumer(ClearRecycler, 8, 8);
+    producer.push_at(20, vec![1]);
+    producer.push_at(10, vec![2]);
+    producer.push_at(20, vec![3]);
+
+    assert!(consumer.pop_due(9).is_none());
+    assert_eq!(3, consumer.pending_count());
+
+    let item = consumer.pop_due(10).unwrap();
+    assert_eq!(10, item.time);
+    assert_eq!(vec![2], item.item);
+    consumer.push_back(item);
+    assert!(consumer.pop_due(19).is_none());
+
+    // Items with the same time are delivered in push order
+    let mut items = Vec::new();
+    while let Some(item) = consumer.pop_due(25) {
+        items.push(item.item[0]);
+        consumer.push_back(item);
+    }
+    assert_eq!(vec![1, 3], items);
+    assert_eq!(0, consumer.pending_count());
+
+    producer.drain_and_recycle();
+    assert_eq!(3, producer.stats().snapshot().recycled);
+}
+
+#[test]
+fn reorder_buffer_never_grows() {
+    let (mut producer, mut consumer) = new_producer_consumer(ClearRecycler, 8, 2);
+    producer.push_at(30, vec![1]);
+    producer.push_at(20, vec![2]);
+    producer.push_at(10, vec![3]);
+
+    // The earliest item is held back in the overflow slot while the buffer is full
+    assert!(consumer.pop_due(0).is_none());
+    assert_eq!(2, consumer.pending_count());
+    assert!(consumer.is_overflowing());
+
+    let mut items = Vec::new();
+    while let Some(item) = consumer.pop_due(30) {
+        items.push(item.item[0]);
+        consumer.push_back(item);
+    }
+    assert_eq!(vec![3, 2, 1], items);
+    assert!(!consumer.is_overflowing());
+}
+
+#[test]
+fn due_overflow_item_is_not_stuck() {
+    let (mut producer, mut consumer) = new_producer_consumer(ClearRecycler, 8, 2);
+    producer.push_at(30, vec![1]);
+    producer.push_at(40, vec![2]);
+    producer.push_at(10, vec![3]);
+
+    let item = consumer.pop_due(10).unwrap();
+    assert_eq!(vec![3], item.item);
+    consumer.push_back(item);
+    assert!(consumer.pop_due(29).is_none());
+    assert!(!consumer.is_overflowing());
+}
+
+#[test]
+fn overflow_might_deliver_out_of_order() {
+    let (mut producer, mut consumer) = new_producer_consumer(ClearRecycler, 8, 1);
+    producer.push_at(20, vec![1]);
+    producer.push_at(30, vec![2]);
+    producer.push_at(10, vec![3]);
+
+    // The item at 10 is still in the queue behind the overflow item
+    let item = consumer.pop_due(20).unwrap();
+    assert!(consumer.is_overflowing());
+    assert_eq!(vec![1], item.item);
+    consumer.push_back(item);
+    let item = consumer.pop_due(20).unwrap();
+    assert_eq!(vec![3], item.item);
+    consumer.push_back(item);
+    assert!(!consumer.is_overflowing());
+    consumer.drain();
+}
+
+#[test]
+fn drain_pending_items() {
+    let (mut producer, mut consumer) = new_producer_consumer(ClearRecycler, 8, 1);
+    producer.push_at(10, vec![1]);
+    producer.push_at(20, vec![2]);
+
+    assert!(consumer.pop_due(0).is_none());
+    consumer.drain();
+    assert_eq!(0, consumer.pending_count());
+    assert!(consumer.pop_due(20).is_none());
+    assert_eq!(2, consumer.stats().snapshot().returned);
+}
+
+#[test]
+#[should_panic(expected = "reorder capacity must not be 0")]
+fn no_reorder_capacity() {
+    let _ = new_producer_consumer::<Vec<u8>, _>(ClearRecycler, 0, 0);
+}
@@ -0,0 +1,46 @@
+// SPDX-FileCopyrightText: The cirque authors
+// SPDX-License-Identifier: MPL-2.0
+
+use cirque::{new_producer_consumer, recyclers::NoOpRecycler, TryPushError};
+
+#[test]
+fn return_all_items_on_shutdown() {
+    // Only a single item could be recycled
+    let (mut producer, mut consumer) = new_producer_consumer(NoOpRecycler, 1);
+    producer.push_iter(1..=4);
+    let item = consumer.pop().unwrap();
+    consumer.push_back(item);
+    producer.drain_and_recycle();
+    assert_eq!(1, producer.recycled_count());
+
+    let item = consumer.pop().unwrap();
+    producer.shutdown();
+    assert_eq!(Err(TryPushError::ShutDown(5)), producer.try_push(5));
+    assert!(!producer.is_shutdown_acknowledged());
+
+    assert!(consumer.is_shutdown_requested());
+    consumer.push_back(item);
+    consumer.acknowledge_shutdown();
+    assert!(producer.is_shutdown_acknowledged());
+
+    let mut items = Vec::new();
+    assert_eq!(4, producer.drain_remaining(&mut items));
+    items.sort_unstable();
+    assert_eq!(vec![1, 2, 3, 4], items);
+    assert_eq!(0, producer.in_flight());
+    assert_eq!(0, producer.dropped_count());
+}
+
+#[test]
+fn shutdown_acknowledged_when_consumer_dropped() {
+    let (mut producer, consumer) = new_producer_consumer(NoOpRecycler, 0);
+    producer.push(1);
+    producer.shutdown();
+    drop(consumer);
+    assert!(producer.is_shutdown_acknowledged());
+    assert_eq!(Err(TryPushError::Disconnected(2)), producer.try_push(2));
+
+    let mut items = Vec::new();
+    assert_eq!(1, producer.drain_remaining(&mut items));
+    assert_eq!(vec![1], items);
+}
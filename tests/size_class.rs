@@ -0,0 +1,163 @@
+// SPDX-FileCopyrightText: The cirque authors
+// SPDX-License-Identifier: MPL-2.0
+
+use cirque::{
+    new_producer_consumer,
+    recyclers::{ClearRecycler, SizeClassRecycler},
+    Producer, Recycler,
+};
+
+/// Push an item with the given minimum capacity and return its actual capacity
+fn push_with_capacity<R>(producer: &mut Producer<Vec<u8>, R>, min_capacity: usize) -> usize
+where
+    R: Recycler<Vec<u8>>,
+{
+    let mut capacity = 0;
+    producer.push_with_capacity(min_capacity, |item| {
+        assert!(item.is_empty());
+        capacity = item.capacity();
+    });
+    capacity
+}
+
+#[test]
+fn picks_smallest_sufficient_size_class() {
+    let recycler = SizeClassRecycler::new(ClearRecycler, Vec::capacity);
+    let (mut producer, mut consumer) = new_producer_consumer(recycler, 3);
+    producer.push_iter([16, 256, 4096].map(Vec::with_capacity));
+    consumer.drain();
+    producer.drain_and_recycle();
+    assert_eq!(3, producer.recycled_count());
+
+    let capacity = push_with_capacity(&mut producer, 200);
+    assert!((256..4096).contains(&capacity));
+    let capacity = push_with_capacity(&mut producer, 10);
+    assert!((16..256).contains(&capacity));
+    let capacity = push_with_capacity(&mut producer, 10);
+    assert!(capacity >= 4096);
+    consumer.drain();
+
+    let stats = producer.stats().snapshot();
+    assert_eq!(3, stats.allocated);
+    assert_eq!(3, stats.reused_recycled);
+}
+
+#[test]
+fn skips_too_small_items_of_the_same_size_class() {
+    let recycler = SizeClassRecycler::new(ClearRecycler, Vec::capacity);
+    let (mut producer, mut consumer) = new_producer_consumer(recycler, 2);
+    producer.push_iter([40, 33].map(Vec::with_capacity));
+    consumer.drain();
+    producer.drain_and_recycle();
+
+    let capacity = push_with_capacity(&mut producer, 40);
+    assert!(capacity >= 40);
+    let capacity = push_with_capacity(&mut producer, 0);
+    assert!((33..40).contains(&capacity));
+    consumer.drain();
+}
+
+#[test]
+fn receives_returned_items_first() {
+    let recycler = SizeClassRecycler::new(ClearRecycler, Vec::capacity);
+    let (mut producer, mut consumer) = new_producer_consumer(recycler, 1);
+    producer.push_iter([1024].map(Vec::with_capacity));
+    consumer.drain();
+
+    let capacity = push_with_capacity(&mut producer, 1000);
+    assert!(capacity >= 1024);
+    consumer.drain();
+
+    let stats = producer.stats().snapshot();
+    assert_eq!(1, stats.allocated);
+    assert_eq!(1, stats.reused_returned);
+    assert_eq!(0, stats.dropped);
+}
+
+#[test]
+fn keeps_returned_items_that_are_too_small() {
+    let recycler = SizeClassRecycler::new(ClearRecycler, Vec::capacity);
+    let (mut producer, mut consumer) = new_producer_consumer(recycler, 2);
+    producer.push_iter([8, 16, 1024].map(Vec::with_capacity));
+    consumer.drain();
+
+    let capacity = push_with_capacity(&mut producer, 1000);
+    assert!(capacity >= 1024);
+    consumer.drain();
+
+    let stats = producer.stats().snapshot();
+    assert_eq!(3, stats.allocated);
+    assert_eq!(2, stats.recycled);
+    assert_eq!(0, stats.dropped);
+}
+
+#[test]
+fn falls_back_to_largest_item() {
+    let recycler = SizeClassRecycler::new(ClearRecycler, Vec::capacity);
+    let (mut producer, mut consumer) = new_producer_consumer(recycler, 2);
+    producer.push_iter([8, 64].map(Vec::with_capacity));
+    consumer.drain();
+    producer.drain_and_recycle();
+
+    let capacity = push_with_capacity(&mut producer, 1000);
+    assert!((64..1000).contains(&capacity));
+    consumer.drain();
+    assert_eq!(2, producer.stats().snapshot().allocated);
+}
+
+#[test]
+fn reuses_returned_item_without_recycling_capacity() {
+    let recycler = SizeClassRecycler::new(ClearRecycler, Vec::capacity);
+    let (mut producer, mut consumer) = new_producer_consumer(recycler, 0);
+    producer.push_iter([64].map(Vec::with_capacity));
+    consumer.drain();
+
+    assert!(push_with_capacity(&mut producer, 64) >= 64);
+    consumer.drain();
+
+    let stats = producer.stats().snapshot();
+    assert_eq!(1, stats.allocated);
+    assert_eq!(1, stats.reused_returned);
+    assert_eq!(0, stats.dropped);
+}
+
+#[test]
+fn reuses_too_small_returned_item_instead_of_dropping_it() {
+    let recycler = SizeClassRecycler::new(ClearRecycler, Vec::capacity);
+    let (mut producer, mut consumer) = new_producer_consumer(recycler, 0);
+    producer.push_iter([16].map(Vec::with_capacity));
+    consumer.drain();
+
+    assert!((16..64).contains(&push_with_capacity(&mut producer, 64)));
+    consumer.drain();
+
+    let stats = producer.stats().snapshot();
+    assert_eq!(1, stats.allocated);
+    assert_eq!(0, stats.dropped);
+}
+
+#[test]
+fn allocates_new_item_if_none_is_available() {
+    let recycler = SizeClassRecycler::new(ClearRecycler, Vec::capacity);
+    let (mut producer, mut consumer) = new_producer_consumer(recycler, 1);
+
+    assert_eq!(0, push_with_capacity(&mut producer, 64));
+    consumer.drain();
+
+    assert_eq!(1, producer.stats().snapshot().allocated);
+}
+
+#[test]
+fn lowering_recycling_capacity_drops_largest_items() {
+    let recycler = SizeClassRecycler::new(ClearRecycler, Vec::capacity);
+    let (mut producer, mut consumer) = new_producer_consumer(recycler, 3);
+    producer.push_iter([8, 4096, 64].map(Vec::with_capacity));
+    consumer.drain();
+    producer.drain_and_recycle();
+
+    producer.tune_recycling_capacity(2);
+    assert_eq!(1, producer.dropped_count());
+    let capacity = push_with_capacity(&mut producer, 100);
+    assert!((64..100).contains(&capacity));
+    consumer.drain();
+}
@@ -0,0 +1,81 @@
+// SPDX-FileCopyrightText: The cirque authors
+// SPDX-License-Identifier: MPL-2.0
+
+use std::thread;
+
+use cirque::{new_producer_consumer, recyclers::ClearRecycler};
+
+#[test]
+fn in_flight() {
+    let (mut producer, mut consumer) = new_producer_consumer(ClearRecycler, 4);
+    producer.push_iter([vec![1u8], vec![2], vec![3]]);
+
+    let stats = producer.stats().snapshot();
+    assert_eq!(3, stats.pushed);
+    assert_eq!(3, stats.in_flight);
+    assert_eq!(3, stats.max_in_flight);
+
+    let item = consumer.pop().unwrap();
+    consumer.push_back(item);
+    let stats = consumer.stats().snapshot();
+    assert_eq!(1, stats.popped);
+    assert_eq!(1, stats.returned);
+    assert_eq!(2, stats.in_flight);
+    assert_eq!(3, stats.max_in_flight);
+
+    consumer.drain();
+    producer.push(vec![4]);
+    let stats = producer.stats().snapshot();
+    assert_eq!(3, stats.returned);
+    assert_eq!(1, stats.in_flight);
+    assert_eq!(3, stats.max_in_flight);
+    consumer.drain();
+}
+
+#[test]
+fn reused_recycled_vs_returned() {
+    let (mut producer, mut consumer) = new_producer_consumer(ClearRecycler, 1);
+    producer.push_iter([vec![1u8], vec![2]]);
+    consumer.drain();
+
+    // Recycles one node and drops the other one that exceeds the capacity
+    producer.drain_and_recycle();
+    producer.push(vec![3]);
+    consumer.drain();
+    // Reuses the returned node directly
+    producer.push(vec![4]);
+    consumer.drain();
+
+    let stats = producer.stats().snapshot();
+    assert_eq!(2, stats.allocated);
+    assert_eq!(1, stats.recycled);
+    assert_eq!(1, stats.dropped);
+    assert_eq!(1, stats.reused_recycled);
+    assert_eq!(1, stats.reused_returned);
+    assert_eq!(
+        stats.pushed,
+        stats.allocated + stats.reused_recycled + stats.reused_returned
+    );
+}
+
+#[test]
+fn monitor_from_another_thread() {
+    let (mut producer, mut consumer) = new_producer_consumer(ClearRecycler, 1);
+    let stats = producer.stats_handle();
+    assert!(producer.is_consumer_alive());
+    assert!(consumer.is_producer_alive());
+
+    producer.push(vec![1u8]);
+    consumer.drain();
+    let monitor_thread = thread::spawn(move || stats.snapshot());
+    let snapshot = monitor_thread.join().unwrap();
+    assert_eq!(1, snapshot.pushed);
+    assert_eq!(1, snapshot.returned);
+
+    // Sharing the statistics doesn't keep the peers alive
+    let stats = consumer.stats_handle();
+    drop(consumer);
+    assert!(!producer.is_consumer_alive());
+    drop(producer);
+    assert_eq!(1, stats.snapshot().pushed);
+}
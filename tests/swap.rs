@@ -0,0 +1,40 @@
+// SPDX-FileCopyrightText: The cirque authors
+// SPDX-License-Identifier: MPL-2.0
+
+use std::{cell::RefCell, rc::Rc};
+
+use cirque::{swap::new_producer_swapper, Recycler};
+
+/// Collects all disposed items
+#[derive(Clone, Default)]
+struct CollectingRecycler {
+    disposed: Rc<RefCell<Vec<u32>>>,
+}
+
+impl Recycler<u32> for CollectingRecycler {
+    fn recycle(&mut self, _item: &mut u32) {}
+
+    fn dispose(&mut self, item: u32) {
+        self.disposed.borrow_mut().push(item);
+    }
+}
+
+#[test]
+fn swap_in_most_recent_state() {
+    let recycler = CollectingRecycler::default();
+    let (mut producer, mut swapper) = new_producer_swapper(recycler.clone(), 0);
+    let mut current = 0;
+    assert!(!swapper.swap_in(&mut current));
+    assert_eq!(0, current);
+
+    producer.push(1);
+    producer.push(2);
+    assert!(swapper.swap_in(&mut current));
+    assert_eq!(2, current);
+    assert!(!swapper.swap_in(&mut current));
+
+    // All displaced states are disposed outside of the realtime context.
+    producer.drain_and_recycle();
+    assert_eq!(vec![0, 1], *recycler.disposed.borrow());
+    assert_eq!(2, swapper.stats().snapshot().returned);
+}
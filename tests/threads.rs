@@ -0,0 +1,39 @@
+// SPDX-FileCopyrightText: The cirque authors
+// SPDX-License-Identifier: MPL-2.0
+
+use std::thread;
+
+use cirque::{new_producer_consumer, recyclers::NoOpRecycler};
+
+const COUNT: usize = 10_000;
+
+#[test]
+fn round_trip_across_threads() {
+    let (mut producer, mut consumer) = new_producer_consumer(NoOpRecycler, 16);
+    let consumer_thread = thread::spawn(move || {
+        let mut expected = 0;
+        while expected < COUNT {
+            if let Some(item) = consumer.pop() {
+                assert_eq!(expected, *item);
+                expected += 1;
+                consumer.push_back(item);
+            }
+        }
+    });
+    for item in 0..COUNT {
+        producer.push(item);
+        if item % 64 == 0 {
+            producer.drain_and_recycle();
+        }
+    }
+    consumer_thread.join().unwrap();
+
+    producer.drain_and_recycle();
+    let stats = producer.stats().snapshot();
+    assert_eq!(COUNT, stats.pushed);
+    assert_eq!(COUNT, stats.returned);
+    assert_eq!(
+        stats.pushed,
+        stats.allocated + stats.reused_recycled + stats.reused_returned
+    );
+}